@@ -38,6 +38,7 @@ __all__ = [
     "Info",
     "PlaylistInfo",
     "Node",
+    "QueuePage",
     "Band",
     "Stats",
     "PlayerUpdate",
@@ -47,6 +48,12 @@ __all__ = [
     "TrackStuck",
     "WebSocketClosed",
     "PlayerDestroyed",
+    "IdleTimeout",
+    "AutoplayTrack",
     "NoSessionPresent",
     "NetworkError",
+    "PlayerError",
+    "LoadFailedError",
+    "NoConnectionInfo",
+    "QueueError",
 ]
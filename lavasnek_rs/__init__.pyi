@@ -1,5 +1,8 @@
+import asyncio
 import typing as t
 
+Snowflake = t.Union[int, str]
+
 class Info:
     length: int
     position: int
@@ -9,7 +12,9 @@ class Info:
     author: str
     title: str
     uri: str
+    length_secs: int
 
+    def formatted_length(self) -> str: ...
     def __new__(
         cls,
         *,
@@ -38,12 +43,27 @@ class Track:
     info: Info
     track: str
 
-    def __new__(cls, track: str, *, info: t.Optional[Info]) -> Track: ...
+    def __new__(cls, track: str, info: t.Optional[Info] = None) -> Track: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
 
 class Tracks:
     tracks: t.List[Track]
     load_type: str
     playlist_info: PlaylistInfo
+    def is_track_loaded(self) -> bool: ...
+    def is_playlist(self) -> bool: ...
+    def is_search_result(self) -> bool: ...
+    def is_no_matches(self) -> bool: ...
+    def is_load_failed(self) -> bool: ...
+    def page(self, offset: int, limit: int) -> t.List[Track]: ...
+    def __len__(self) -> int: ...
+    @t.overload
+    def __getitem__(self, key: int) -> Track: ...
+    @t.overload
+    def __getitem__(self, key: slice) -> t.List[Track]: ...
+    def __iter__(self) -> t.Iterator[Track]: ...
 
 class TrackStuck:
     track: str
@@ -54,9 +74,17 @@ class TrackStuck:
 
 class TrackQueue:
     start_time: int
-    requester: int
+    requester: t.Optional[int]
     track: Track
-    end_time: int
+    end_time: t.Optional[int]
+    def __new__(
+        cls,
+        track: Track,
+        start_time: int = 0,
+        end_time: t.Optional[int] = None,
+        requester: t.Optional[int] = None,
+    ) -> TrackQueue: ...
+    def remaining_ms(self, position: int) -> t.Optional[int]: ...
 
 class Node:
     volume: int
@@ -65,13 +93,29 @@ class Node:
     now_playing: t.Optional[TrackQueue]
     is_paused: bool
     queue: t.List[TrackQueue]
+    def queue_len(self) -> int: ...
+    def total_count(self) -> int: ...
+    def queue_at(self, index: int) -> t.Optional[TrackQueue]: ...
+    def player_state(self) -> t.Literal["playing", "paused", "stopped"]: ...
     def get_data(self) -> t.Union[t.Any, t.Dict[t.Any, t.Any]]: ...
+    def get_or_init_data(self, factory: t.Callable[[], t.Any]) -> t.Any: ...
     def set_data(self, data: t.Union[t.Any, t.Dict[t.Any,  t.Any]]) -> None: ...
+    def update_data(self, key: t.Any, value: t.Any) -> None: ...
+
+class QueuePage:
+    tracks: t.List[TrackQueue]
+    page: int
+    total_tracks: int
+    total_pages: int
 
 class Band:
     gain: float
     band: int
     def __new__(cls, band: int, gain: float) -> Band: ...
+    @staticmethod
+    def from_db_gain(band: int, db: float) -> Band: ...
+    @staticmethod
+    def bass_boost(amount: float) -> t.List[Band]: ...
 
 class Stats:
     frame_stats_deficit: t.Optional[int]
@@ -93,6 +137,7 @@ class PlayerUpdate:
     guild_id: int
     state_time: int
     state_position: int
+    state_ping_ms: int
     op: str
 
 class TrackStart:
@@ -100,6 +145,7 @@ class TrackStart:
     guild_id: int
     track: str
     op: str
+    info: t.Optional[Info]
 
 class TrackFinish:
     reason: str
@@ -107,6 +153,12 @@ class TrackFinish:
     track_finish_type: str
     guild_id: int
     track: str
+    def is_natural_finish(self) -> bool: ...
+    def is_load_failed(self) -> bool: ...
+    def is_stopped(self) -> bool: ...
+    def is_replaced(self) -> bool: ...
+    def is_cleanup(self) -> bool: ...
+    def may_start_next(self) -> bool: ...
 
 class TrackException:
     error: str
@@ -117,72 +169,204 @@ class TrackException:
     guild_id: int
     exception_cause: str
     exception_severity: str
+    def is_common(self) -> bool: ...
+    def is_suspicious(self) -> bool: ...
+    def is_fault(self) -> bool: ...
 
 class NoSessionPresent(Exception): ...
 class NetworkError(Exception): ...
+class PlayerError(NetworkError): ...
+class LoadFailedError(Exception): ...
+class NoConnectionInfo(TimeoutError): ...
+class QueueError(Exception): ...
 class WebsocketClosed(Exception): ...
 class PlayerDestroyed(Exception): ...
 
+class IdleTimeout:
+    guild_id: int
+    disconnected: bool
+
+class AutoplayTrack:
+    guild_id: int
+    track: Track
+
 class PlayBuilder:
     async def start(self) -> None: ...
-    async def queue(self) -> None: ...
+    async def queue(self) -> int: ...
+    async def add_to_node_without_loop(self) -> int: ...
     def to_track_queue(self) -> TrackQueue: ...
     def requester(self, requester: int) -> PlayBuilder: ...
+    def volume(self, volume: int) -> PlayBuilder: ...
     def replace(self, replace: bool) -> PlayBuilder: ...
     def start_time_secs(self, start: int) -> PlayBuilder: ...
     def finish_time_secs(self, finish: int) -> PlayBuilder: ...
     def start_time_millis(self, start: int) -> PlayBuilder: ...
     def finish_time_millis(self, finish: int) -> PlayBuilder: ...
+    def trim_end_secs(self, secs: int) -> PlayBuilder: ...
+    def trim_end_millis(self, millis: int) -> PlayBuilder: ...
 
 class Lavalink:
-    def play(self, guild_id: int, track: Track) -> PlayBuilder: ...
+    def play(self, guild_id: Snowflake, track: Track) -> PlayBuilder: ...
+    async def play_segment(
+        self,
+        guild_id: Snowflake,
+        track: Track,
+        start_ms: int,
+        end_ms: int,
+        requester: t.Optional[Snowflake] = None,
+    ) -> int: ...
+    async def play_or_queue(
+        self,
+        guild_id: Snowflake,
+        track: Track,
+        requester: t.Optional[Snowflake] = None,
+    ) -> t.Literal["playing", "queued"]: ...
+    async def queue_raw(
+        self,
+        guild_id: Snowflake,
+        tracks: t.List[str],
+        requester: t.Optional[Snowflake] = None,
+    ) -> None: ...
     async def start_discord_gateway(self, wait_time: int) -> None: ...
-    async def join(self, guild_id: int, channel_id: int) -> ConnectionInfo: ...
-    async def leave(self, guild_id: int) -> None: ...
-    async def create_session(self, connection_info: ConnectionInfo) -> None: ...
-    async def destroy(self, guild_id: int) -> None: ...
-    async def get_tracks(self, query: str) -> Tracks: ...
-    async def auto_search_tracks(self, query: str) -> Tracks: ...
+    async def join(
+        self, guild_id: Snowflake, channel_id: Snowflake, timeout_secs: t.Optional[int] = None
+    ) -> ConnectionInfo: ...
+    async def leave(self, guild_id: Snowflake, timeout_secs: t.Optional[int] = None) -> None: ...
+    async def join_and_create(self, guild_id: Snowflake, channel_id: Snowflake) -> ConnectionInfo: ...
+    async def move_to(self, guild_id: Snowflake, channel_id: Snowflake) -> ConnectionInfo: ...
+    async def handle_voice_disconnect(
+        self, guild_id: Snowflake, code: int
+    ) -> t.Literal["reconnected", "disconnected", "ignored"]: ...
+    async def create_session(self, connection_info: ConnectionInfo) -> bool: ...
+    async def leave_if_alone(self, guild_id: Snowflake, user_count: int) -> bool: ...
+    async def destroy(self, guild_id: Snowflake) -> None: ...
+    async def flush_guild(self, guild_id: Snowflake, destroy: bool = False) -> None: ...
+    async def destroy_all(self, leave: bool = False) -> t.Dict[int, str]: ...
+    async def get_tracks(self, query: str, raise_on_failure: bool = False) -> Tracks: ...
+    async def auto_search_tracks(
+        self, query: str, raise_on_failure: bool = False
+    ) -> Tracks: ...
     async def search_tracks(self, query: str) -> Tracks: ...
+    async def search_first(self, query: str) -> t.Optional[Track]: ...
+    async def search_suggestions(
+        self, query: str, limit: int = 5
+    ) -> t.List[t.Tuple[str, str]]: ...
+    async def search_tracks_with_source(
+        self,
+        query: str,
+        source: t.Literal[
+            "youtube", "youtube_music", "soundcloud", "spotify", "apple_music", "deezer"
+        ] = "youtube",
+    ) -> Tracks: ...
+    async def node_info(self) -> t.Dict[str, t.Any]: ...
+    async def ping(self) -> float: ...
+    async def get_players(self) -> t.List[t.Dict[str, t.Any]]: ...
+    def get_stats(self) -> t.Optional[Stats]: ...
+    def set_event_loop(
+        self, event_loop: t.Optional[asyncio.AbstractEventLoop] = None
+    ) -> None: ...
     async def decode_track(self, track: str) -> Info: ...
-    async def skip(self, guild_id: int) -> t.Optional[TrackQueue]: ...
-    async def stop(self, guild_id: int) -> None: ...
-    async def set_pause(self, guild_id: int, pause: bool) -> None: ...
-    async def pause(self, guild_id: int) -> None: ...
-    async def resume(self, guild_id: int) -> None: ...
-    async def seek_secs(self, guild_id: int, time: int) -> None: ...
-    async def jump_to_time_secs(self, guild_id: int, time: int) -> None: ...
-    async def scrub_secs(self, guild_id: int, time: int) -> None: ...
-    async def seek_millis(self, guild_id: int, time: int) -> None: ...
-    async def jump_to_time_millis(self, guild_id: int, time: int) -> None: ...
-    async def scrub_millis(self, guild_id: int, time: int) -> None: ...
-    async def volume(self, guild_id: int, volume: int) -> None: ...
-    async def equalize_all(self, guild_id: int, bands: t.List[float]) -> None: ...
-    async def equalize_dynamic(self, guild_id: int, bands: t.List[Band]) -> None: ...
-    async def equalize_band(self, guild_id: int, band: Band) -> None: ...
-    async def equalize_reset(self, guild_id: int) -> None: ...
-    async def remove_guild_from_loops(self, guild_id: int) -> None: ...
-    async def remove_guild_node(self, guild_id: int) -> None: ...
-    async def get_guild_node(self, guild_id: int) -> t.Optional[Node]: ...
-    async def set_guild_node(self, guild_id: int, node: Node) -> None: ...
+    async def skip(self, guild_id: Snowflake) -> t.Optional[TrackQueue]: ...
+    async def skip_many(self, guild_id: Snowflake, count: int) -> t.Optional[TrackQueue]: ...
+    async def replace_now_playing(
+        self, guild_id: Snowflake, track: Track, requeue: bool = True
+    ) -> t.Optional[TrackQueue]: ...
+    async def stop(self, guild_id: Snowflake) -> None: ...
+    async def set_pause(self, guild_id: Snowflake, pause: bool) -> None: ...
+    async def pause(self, guild_id: Snowflake) -> None: ...
+    async def resume(self, guild_id: Snowflake) -> None: ...
+    async def pause_toggle(self, guild_id: Snowflake) -> t.Optional[bool]: ...
+    async def set_pause_all(self, pause: bool) -> t.Tuple[t.List[int], t.Dict[int, str]]: ...
+    async def pause_all(self) -> t.Tuple[t.List[int], t.Dict[int, str]]: ...
+    async def resume_all(self) -> t.Tuple[t.List[int], t.Dict[int, str]]: ...
+    async def seek_secs(self, guild_id: Snowflake, time: int) -> None: ...
+    async def jump_to_time_secs(self, guild_id: Snowflake, time: int) -> None: ...
+    async def scrub_secs(self, guild_id: Snowflake, time: int) -> None: ...
+    async def seek_millis(self, guild_id: Snowflake, time: int) -> None: ...
+    async def jump_to_time_millis(self, guild_id: Snowflake, time: int) -> None: ...
+    async def scrub_millis(self, guild_id: Snowflake, time: int) -> None: ...
+    async def restart_track(self, guild_id: Snowflake) -> bool: ...
+    async def replay(self, guild_id: Snowflake) -> bool: ...
+    async def seek_to_percent(self, guild_id: Snowflake, percent: float) -> bool: ...
+    async def volume(self, guild_id: Snowflake, volume: int) -> None: ...
+    async def set_volume_all(self, volume: int) -> t.Dict[int, str]: ...
+    async def fade_volume(
+        self, guild_id: Snowflake, target: int, duration_ms: int, steps: int = 10
+    ) -> bool: ...
+    async def equalize_all(self, guild_id: Snowflake, bands: t.List[float]) -> None: ...
+    async def equalize_dynamic(self, guild_id: Snowflake, bands: t.List[Band]) -> None: ...
+    async def equalize_band(self, guild_id: Snowflake, band: Band) -> None: ...
+    async def equalize_reset(self, guild_id: Snowflake) -> None: ...
+    async def get_queue_page(
+        self, guild_id: Snowflake, page: int, per_page: int
+    ) -> QueuePage: ...
+    async def save_queue(
+        self, guild_id: Snowflake, include_now_playing: bool = True
+    ) -> t.List[t.Dict[str, t.Any]]: ...
+    async def load_queue(
+        self, guild_id: Snowflake, queue: t.List[t.Dict[str, t.Any]], start: bool = True
+    ) -> int: ...
+    async def remove_duplicates(
+        self, guild_id: Snowflake, by: t.Literal["track", "uri"] = "track"
+    ) -> int: ...
+    async def previous(self, guild_id: Snowflake) -> t.Optional[TrackQueue]: ...
+    def autoplay(self, guild_id: Snowflake, enabled: bool) -> None: ...
+    def get_history(self, guild_id: Snowflake) -> t.List[TrackQueue]: ...
+    async def set_requester(
+        self,
+        guild_id: Snowflake,
+        index: t.Optional[int],
+        user_id: t.Optional[Snowflake],
+    ) -> None: ...
+    async def remove_user_tracks(
+        self, guild_id: Snowflake, user_id: Snowflake, include_now_playing: bool = False
+    ) -> int: ...
+    async def remove_guild_from_loops(self, guild_id: Snowflake) -> None: ...
+    async def remove_guild_node(self, guild_id: Snowflake) -> None: ...
+    async def get_guild_node(self, guild_id: Snowflake) -> t.Optional[Node]: ...
+    async def get_guild_nodes(self, guild_ids: t.List[Snowflake]) -> t.Dict[int, Node]: ...
+    def try_get_guild_node(self, guild_id: Snowflake) -> t.Optional[Node]: ...
+    async def get_node_data(
+        self, guild_id: Snowflake
+    ) -> t.Optional[t.Union[t.Any, t.Dict[t.Any, t.Any]]]: ...
+    async def set_node_data(
+        self, guild_id: Snowflake, data: t.Union[t.Any, t.Dict[t.Any, t.Any]]
+    ) -> None: ...
+    async def get_guild_node_or_default(self, guild_id: Snowflake) -> Node: ...
+    async def active_guilds(self) -> t.List[int]: ...
+    async def playing_guilds(self) -> t.List[int]: ...
+    def playing_since(self, guild_id: Snowflake) -> t.Optional[int]: ...
+    def player_latency(self, guild_id: Snowflake) -> t.Optional[int]: ...
+    def loop_count(self, guild_id: Snowflake) -> int: ...
+    async def current_track_info(self, guild_id: Snowflake) -> t.Optional[Info]: ...
+    async def set_guild_node(self, guild_id: Snowflake, node: Node) -> None: ...
+    async def transfer_node(
+        self, src_guild: Snowflake, dst_guild: Snowflake, overwrite: bool = False
+    ) -> None: ...
+    async def update_node(
+        self, guild_id: Snowflake, callback: t.Callable[[Node], None]
+    ) -> bool: ...
     def get_guild_gateway_connection_info(
-        self, guild_id: int
+        self, guild_id: Snowflake
     ) -> t.Optional[ConnectionInfo]: ...
     async def wait_for_full_connection_info_insert(
-        self, guild_id: int, event_count: int = 10
+        self, guild_id: Snowflake, event_count: int = 10
     ) -> ConnectionInfo: ...
+    async def wait_for_track_start(
+        self, guild_id: Snowflake, timeout_secs: int
+    ) -> TrackStart: ...
     async def wait_for_connection_info_remove(
-        self, guild_id: int, event_count: int = 10
+        self, guild_id: Snowflake, event_count: int = 10
     ) -> None: ...
     async def raw_handle_event_voice_server_update(
-        self, guild_id: int, endpoint: str, token: str
+        self, guild_id: Snowflake, endpoint: str, token: str
     ) -> None: ...
     def raw_handle_event_voice_state_update(
-        self, guild_id: int, user_id: int, session_id: str, channel_id: t.Optional[int]
+        self, guild_id: Snowflake, user_id: Snowflake, session_id: str, channel_id: t.Optional[Snowflake]
     ) -> None: ...
 
 class LavalinkBuilder:
-    async def build(cls, event_handler: object) -> Lavalink: ...
+    async def build(cls, event_handler: t.Union[object, t.Type[object]]) -> Lavalink: ...
     def __new__(self, bot_id: int, token: str) -> LavalinkBuilder: ...
     def set_host(self, host: str) -> LavalinkBuilder: ...
     def set_port(self, port: int) -> LavalinkBuilder: ...
@@ -190,11 +374,31 @@ class LavalinkBuilder:
     def set_password(self, password: str) -> LavalinkBuilder: ...
     def set_shard_count(self, shard_count: int) -> LavalinkBuilder: ...
     def set_bot_id(self, bot_id: int) -> LavalinkBuilder: ...
+    def set_user_id(self, user_id: int) -> LavalinkBuilder: ...
     def set_bot_token(self, bot_token: str) -> LavalinkBuilder: ...
     def set_is_ssl(self, is_ssl: bool) -> LavalinkBuilder: ...
     def set_start_gateway(self, start_gateway: bool) -> LavalinkBuilder: ...
     def set_gateway_start_wait_time_secs(self, time: int) -> LavalinkBuilder: ...
     def set_gateway_start_wait_time_millis(self, time: int) -> LavalinkBuilder: ...
+    def set_voice_timeout_secs(self, time: int) -> LavalinkBuilder: ...
+    def set_voice_timeout_millis(self, time: int) -> LavalinkBuilder: ...
+    def set_history_size(self, size: int) -> LavalinkBuilder: ...
+    def set_search_cache(self, size: int, ttl_secs: int) -> LavalinkBuilder: ...
+    def set_manage_queue(self, manage_queue: bool) -> LavalinkBuilder: ...
+    def set_search_sources(self, sources: t.List[str]) -> LavalinkBuilder: ...
+    def set_error_handler(
+        self,
+        error_handler: t.Callable[
+            [BaseException, str], t.Optional[t.Awaitable[None]]
+        ],
+    ) -> LavalinkBuilder: ...
+    def set_idle_timeout_secs(self, time: int) -> LavalinkBuilder: ...
+    def set_idle_timeout_millis(self, time: int) -> LavalinkBuilder: ...
+    def set_idle_disconnect(self, idle_disconnect: bool) -> LavalinkBuilder: ...
+    def set_rest_timeout_secs(self, time: int) -> LavalinkBuilder: ...
+    def set_rest_timeout_millis(self, time: int) -> LavalinkBuilder: ...
+    def set_rest_retries(self, count: int) -> LavalinkBuilder: ...
+    def set_rest_backoff_millis(self, time: int) -> LavalinkBuilder: ...
 
 class LavalinkEventHandler:
     async def stats(self, client: Lavalink, event: Stats) -> None: ...
@@ -213,3 +417,9 @@ class LavalinkEventHandler:
     async def player_destroyed(
         self, client: Lavalink, event: PlayerDestroyed
     ) -> None: ...
+    async def idle_timeout(
+        self, client: Lavalink, event: IdleTimeout
+    ) -> None: ...
+    async def autoplay_track(
+        self, client: Lavalink, event: AutoplayTrack
+    ) -> None: ...
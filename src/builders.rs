@@ -1,9 +1,11 @@
+use crate::data::{LavalinkConfig, LavalinkData, LavalinkRest, SearchCacheConfig};
 use crate::error;
 use crate::events;
-use crate::model::TrackQueue;
+use crate::model::{validate_volume, TrackQueue};
+use crate::sources;
 use crate::Lavalink;
 
-use pyo3::prelude::*;
+use pyo3::{prelude::*, types::PyType};
 
 use lavalink_rs::{
     builders::{LavalinkClientBuilder, PlayParameters},
@@ -13,6 +15,7 @@ use lavalink_rs::{
 };
 use std::net::SocketAddr;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// __new__()
@@ -29,11 +32,16 @@ use std::time::Duration;
 #[pyo3(text_signature = "(bot_id, bot_token, /)")]
 pub struct LavalinkBuilder {
     pub builder: LavalinkClientBuilder,
+    pub config: LavalinkConfig,
 }
 
 #[pyclass]
 pub struct PlayBuilder {
     pub builder: PlayParameters,
+    pub lava: LavalinkClient,
+    pub guild_id: u64,
+    pub volume: Option<u16>,
+    pub data: Arc<LavalinkData>,
 }
 
 #[pymethods]
@@ -42,7 +50,10 @@ impl LavalinkBuilder {
     fn new(bot_id: u64, token: String) -> Self {
         let builder = LavalinkClient::builder(bot_id, &token);
 
-        Self { builder }
+        Self {
+            builder,
+            config: LavalinkConfig::default(),
+        }
     }
 
     /// Uses the Self data to build a Lavalink client and return it.
@@ -50,25 +61,52 @@ impl LavalinkBuilder {
     /// Can raise an exception if it's unable to connect to the lavalink server, discord server, or
     /// both.
     ///
+    /// Raises `ValueError` right away if the bot ID is not a valid discord snowflake, as voice
+    /// connections would not work with it.
+    ///
+    /// The event handler can be an instance, which will be used as is, so it can hold any state
+    /// the events need, like a reference to the bot. If a class is passed, it's instantiated once
+    /// without arguments.
+    ///
     /// Positional Arguments:
-    /// - `event_handler` : `impl LavalinkEventHandler`
+    /// - `event_handler` : `impl LavalinkEventHandler` or `Type<impl LavalinkEventHandler>`
     ///
     /// Returns: `Future<Result<Lavalink, builtins.ConnectionError>>`
     #[pyo3(text_signature = "($self, event_handler, /)")]
     fn build<'a>(&self, py: Python<'a>, event_handler: PyObject) -> PyResult<&'a PyAny> {
+        validate_snowflake("bot_id", self.builder.bot_id.0)?;
+
+        let event_handler = if event_handler.as_ref(py).downcast::<PyType>().is_ok() {
+            event_handler.call0(py)?
+        } else {
+            event_handler
+        };
+
+        events::warn_misspelled_events(py, event_handler.as_ref(py))?;
+
         let builder = self.builder.clone();
+        let rest = LavalinkRest::new(
+            &self.builder.host,
+            self.builder.port,
+            self.builder.is_ssl,
+            self.builder.password.clone(),
+        );
         let current_loop = pyo3_asyncio::get_running_loop(py)?;
-        let loop_ref = PyObject::from(current_loop);
+        let data = Arc::new(LavalinkData::new(
+            self.config.clone(),
+            rest,
+            PyObject::from(current_loop),
+        ));
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
             let lava = builder
                 .build(events::LavalinkEventHandler {
                     inner: event_handler,
-                    current_loop: loop_ref,
+                    data: data.clone(),
                 })
                 .await
                 .map_err(|e| error::ConnectionError::new_err(e.to_string()))?;
-            let lavalink = Lavalink { lava };
+            let lavalink = Lavalink { lava, data };
 
             Ok(Python::with_gil(|py| lavalink.into_py(py)))
         })
@@ -153,6 +191,14 @@ impl LavalinkBuilder {
         slf
     }
 
+    /// Alias to `set_bot_id()`
+    ///
+    /// This is the user ID of the bot, which is not always the same as the application ID.
+    #[pyo3(text_signature = "($self, user_id, /)")]
+    fn set_user_id(slf: PyRefMut<Self>, user_id: u64) -> PyRefMut<Self> {
+        Self::set_bot_id(slf, user_id)
+    }
+
     /// Sets the token of the bot.
     ///
     /// Positional Arguments:
@@ -213,23 +259,253 @@ impl LavalinkBuilder {
         slf.builder.gateway_start_wait_time = Duration::from_millis(time);
         slf
     }
+
+    /// Sets the time to wait for `Lavalink.join()` and `Lavalink.leave()` to finish before raising
+    /// `TimeoutError`. (Default to: 5 seconds)
+    ///
+    /// Positional Arguments:
+    /// - `time` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, time, /)")]
+    fn set_voice_timeout_secs(mut slf: PyRefMut<Self>, time: u64) -> PyRefMut<Self> {
+        slf.config.voice_timeout = Duration::from_secs(time);
+        slf
+    }
+
+    /// Sets the time to wait for `Lavalink.join()` and `Lavalink.leave()` to finish before raising
+    /// `TimeoutError`.
+    ///
+    /// Positional Arguments:
+    /// - `time` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, time, /)")]
+    fn set_voice_timeout_millis(mut slf: PyRefMut<Self>, time: u64) -> PyRefMut<Self> {
+        slf.config.voice_timeout = Duration::from_millis(time);
+        slf
+    }
+
+    /// Sets the time a guild can have nothing playing after a track finishes before the
+    /// `idle_timeout` event is sent. The timer starts again every time a track finishes, and stops
+    /// when a track starts. (Default to: disabled)
+    ///
+    /// Positional Arguments:
+    /// - `time` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, time, /)")]
+    fn set_idle_timeout_secs(mut slf: PyRefMut<Self>, time: u64) -> PyRefMut<Self> {
+        slf.config.idle_timeout = Some(Duration::from_secs(time));
+        slf
+    }
+
+    /// Sets the time a guild can have nothing playing after a track finishes before the
+    /// `idle_timeout` event is sent.
+    ///
+    /// Positional Arguments:
+    /// - `time` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, time, /)")]
+    fn set_idle_timeout_millis(mut slf: PyRefMut<Self>, time: u64) -> PyRefMut<Self> {
+        slf.config.idle_timeout = Some(Duration::from_millis(time));
+        slf
+    }
+
+    /// Sets if the session should be destroyed, and the bot should leave the voice channel, when
+    /// the idle timeout runs out, before sending the `idle_timeout` event. (Default to: False)
+    ///
+    /// Positional Arguments:
+    /// - `idle_disconnect` : `bool`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, idle_disconnect, /)")]
+    fn set_idle_disconnect(mut slf: PyRefMut<Self>, idle_disconnect: bool) -> PyRefMut<Self> {
+        slf.config.idle_disconnect = idle_disconnect;
+        slf
+    }
+
+    /// Sets if `PlayBuilder.queue()` starts a queue loop that plays the tracks of the queue one after
+    /// the other. (Default to: True)
+    ///
+    /// If `False`, `queue()` only adds the tracks to the Node, like
+    /// `PlayBuilder.add_to_node_without_loop()`, and they only play when `PlayBuilder.start()` is
+    /// called, for bots with their own scheduler.
+    ///
+    /// Positional Arguments:
+    /// - `manage_queue` : `bool`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, manage_queue, /)")]
+    fn set_manage_queue(mut slf: PyRefMut<Self>, manage_queue: bool) -> PyRefMut<Self> {
+        slf.config.manage_queue = manage_queue;
+        slf
+    }
+
+    /// Sets how many of the tracks that finished playing are remembered per guild for
+    /// `Lavalink.get_history()`. (Default to: 50)
+    ///
+    /// Positional Arguments:
+    /// - `size` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, size, /)")]
+    fn set_history_size(mut slf: PyRefMut<Self>, size: usize) -> PyRefMut<Self> {
+        slf.config.history_size = size;
+        slf
+    }
+
+    /// Caches the results of `Lavalink.search_tracks()` and `Lavalink.auto_search_tracks()`, so
+    /// repeating a search within `ttl_secs` seconds doesn't send it to Lavalink again. Up to `size`
+    /// results are kept, removing the least recently used ones first. (Default to: no cache)
+    ///
+    /// Queries are compared ignoring case and extra whitespace. URLs and searches that found
+    /// nothing are never cached.
+    ///
+    /// Positional Arguments:
+    /// - `size` : `Unsigned 64 bit integer`
+    /// - `ttl_secs` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, size, ttl_secs, /)")]
+    fn set_search_cache(mut slf: PyRefMut<Self>, size: usize, ttl_secs: u64) -> PyRefMut<Self> {
+        slf.config.search_cache = Some(SearchCacheConfig {
+            size,
+            ttl: Duration::from_secs(ttl_secs),
+        });
+        slf
+    }
+
+    /// Sets the sources `Lavalink.auto_search_tracks()` searches on, in order, until one of them
+    /// finds something. URLs are still loaded directly. (Default to: youtube)
+    ///
+    /// The valid sources are the same as `Lavalink.search_tracks_with_source()`.
+    ///
+    /// ```py
+    /// builder.set_search_sources(["youtube_music", "youtube", "soundcloud"])
+    /// ```
+    ///
+    /// Positional Arguments:
+    /// - `sources` : `List<String>`
+    ///
+    /// Returns: `Result<Self, [builtins.ValueError]>`
+    #[pyo3(text_signature = "($self, sources, /)")]
+    fn set_search_sources(
+        mut slf: PyRefMut<Self>,
+        sources: Vec<String>,
+    ) -> PyResult<PyRefMut<Self>> {
+        slf.config.search_sources = sources
+            .iter()
+            .map(|name| sources::source_by_name(name))
+            .collect::<PyResult<_>>()?;
+        Ok(slf)
+    }
+
+    /// Sets the function that receives the exceptions raised by the events, instead of printing
+    /// them to stderr, like to send them to a logger.
+    ///
+    /// It's called with the exception and the name of the event, and it can be async. If it
+    /// raises an exception itself, both exceptions are printed.
+    ///
+    /// ```py
+    /// async def on_error(error, event_name):
+    ///     logger.error(f"Error on the {event_name} event", exc_info=error)
+    ///
+    /// builder.set_error_handler(on_error)
+    /// ```
+    ///
+    /// Positional Arguments:
+    /// - `error_handler` : `Callable<[Exception, String], Optional<Future<None>>>`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, error_handler, /)")]
+    fn set_error_handler(mut slf: PyRefMut<Self>, error_handler: PyObject) -> PyRefMut<Self> {
+        slf.config.error_handler = Some(error_handler);
+        slf
+    }
+
+    /// Sets the time to wait for the REST requests to the Lavalink server, like `get_tracks()` or
+    /// `decode_track()`, before raising `TimeoutError`. (Default to: no timeout)
+    ///
+    /// Positional Arguments:
+    /// - `time` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, time, /)")]
+    fn set_rest_timeout_secs(mut slf: PyRefMut<Self>, time: u64) -> PyRefMut<Self> {
+        slf.config.rest_timeout = Some(Duration::from_secs(time));
+        slf
+    }
+
+    /// Sets the time to wait for the REST requests to the Lavalink server, like `get_tracks()` or
+    /// `decode_track()`, before raising `TimeoutError`.
+    ///
+    /// Positional Arguments:
+    /// - `time` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, time, /)")]
+    fn set_rest_timeout_millis(mut slf: PyRefMut<Self>, time: u64) -> PyRefMut<Self> {
+        slf.config.rest_timeout = Some(Duration::from_millis(time));
+        slf
+    }
+
+    /// Sets how many times the requests that load tracks, like `get_tracks()`, `search_tracks()`
+    /// or `decode_track()`, are retried when they raise `NetworkError` or `TimeoutError`, before
+    /// raising the error. Requests that change the player are never retried. (Default to: 0)
+    ///
+    /// The time between retries is set with `set_rest_backoff_millis()`, and it doubles on every
+    /// retry.
+    ///
+    /// Positional Arguments:
+    /// - `count` : `Unsigned 32 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, count, /)")]
+    fn set_rest_retries(mut slf: PyRefMut<Self>, count: u32) -> PyRefMut<Self> {
+        slf.config.rest_retries.count = count;
+        slf
+    }
+
+    /// Sets the time to wait before the first retry of a failed request, see
+    /// `set_rest_retries()`. (Default to: 500)
+    ///
+    /// Positional Arguments:
+    /// - `time` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, time, /)")]
+    fn set_rest_backoff_millis(mut slf: PyRefMut<Self>, time: u64) -> PyRefMut<Self> {
+        slf.config.rest_retries.backoff = Duration::from_millis(time);
+        slf
+    }
 }
 
 #[pymethods]
 impl PlayBuilder {
-    // NOTE: this can only return a network error.
     /// Starts playing the track.
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
+    /// If a volume was set with `volume()`, it's applied right after the track starts.
+    ///
+    /// Returns: `Future<Result<None, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
     #[pyo3(text_signature = "($self, /)")]
     fn start<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
         let builder = self.builder.clone();
+        let lava_client = self.lava.clone();
+        let guild_id = self.guild_id;
+        let volume = self.volume;
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            builder
-                .start()
-                .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+            builder.start().await.map_err(error::player_error)?;
+
+            if let Some(volume) = volume {
+                lava_client
+                    .volume(guild_id, volume)
+                    .await
+                    .map_err(error::player_error)?;
+            }
+
             Ok(Python::with_gil(|py| py.None()))
         })
     }
@@ -240,22 +516,77 @@ impl PlayBuilder {
     /// If there's no queue loop running, this will start one up, and add it to the running loops
     /// on `Lavalink.loops()`
     ///
+    /// If the queue loop was disabled with `LavalinkBuilder.set_manage_queue()`, this is the same
+    /// as `add_to_node_without_loop()`.
+    ///
     /// Needs for `Lavalink.create_session() to be called first.
     ///
-    /// Returns: `Future<Result<None, [lavasnek_rs.NoSessionPresent, lavasnek_rs.NetworkError]>>`
+    /// Returns the 0 based index of the track on `Node.queue`. The current track is the first one
+    /// of the queue, so 0 means that the track is the one playing now, or it starts right away
+    /// because nothing was playing.
+    ///
+    /// Raises `QueueError` if lavalink-rs fails to queue the track for any other reason.
+    ///
+    /// Returns: `Future<Result<Unsigned 64 bit integer, [lavasnek_rs.NoSessionPresent, lavasnek_rs.NetworkError, lavasnek_rs.QueueError]>>`
     #[pyo3(text_signature = "($self, /)")]
-    fn queue<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+    pub fn queue<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
         let builder = self.builder.clone();
+        let lava_client = self.lava.clone();
+        let data = self.data.clone();
+        let guild_id = self.guild_id;
+        let volume = self.volume;
+        let track_queue = self.to_track_queue().inner;
+        let track = track_queue.track.track.clone();
+
+        if !self.data.config.manage_queue {
+            return pyo3_asyncio::tokio::future_into_py(py, async move {
+                let position = add_to_node(&lava_client, guild_id, track_queue).await?;
+
+                if let Some(volume) = volume {
+                    add_pending_volume(&data, guild_id, track, volume);
+                }
+
+                Ok(position)
+            });
+        }
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            builder.queue().await.map_err(|e| match e {
-                LavalinkError::NoSessionPresent => error::NoSessionPresent::new_err(e.to_string()),
-                LavalinkError::ErrorWebsocketPayload(_) => {
-                    error::NetworkError::new_err(e.to_string())
+            let position = queue_with_loop(&lava_client, guild_id, builder, track_queue).await?;
+
+            match volume {
+                // Nothing was playing, so the volume of the player can be changed right away.
+                Some(volume) if position == 0 => {
+                    lava_client
+                        .volume(guild_id, volume)
+                        .await
+                        .map_err(error::player_error)?;
                 }
-                _ => error::Exception::new_err(e.to_string()),
-            })?;
-            Ok(Python::with_gil(|py| py.None()))
+                Some(volume) => add_pending_volume(&data, guild_id, track, volume),
+                None => (),
+            }
+
+            Ok(position)
+        })
+    }
+
+    /// Adds the track to the node queue, without starting a queue loop.
+    ///
+    /// This is meant for custom schedulers, the Node is only used to store the tracks, and the
+    /// next track will only play when `start()` is called, so `Node.is_on_loops` stays `False`.
+    ///
+    /// Needs for `Lavalink.create_session() to be called first.
+    ///
+    /// Returns the 0 based index of the track on `Node.queue`.
+    ///
+    /// Returns: `Future<Result<Unsigned 64 bit integer, lavasnek_rs.NoSessionPresent>>`
+    #[pyo3(text_signature = "($self, /)")]
+    fn add_to_node_without_loop<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+        let guild_id = self.guild_id;
+        let track_queue = self.to_track_queue().inner;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            add_to_node(&lava_client, guild_id, track_queue).await
         })
     }
 
@@ -290,6 +621,27 @@ impl PlayBuilder {
         slf
     }
 
+    /// Sets the volume the player will have when the track starts. Max is 1000, min is 0
+    ///
+    /// This sets the volume of the player, not a gain for this track only, so the tracks played
+    /// after this one will keep the same volume unless it's changed again.
+    ///
+    /// With `queue()`, the volume is applied when the track starts playing, so the tracks before it
+    /// keep the volume they have.
+    ///
+    /// Raises `ValueError` if the volume is above 1000.
+    ///
+    /// Positional Arguments:
+    /// - `volume` : `Unsigned 16 bit integer`
+    ///
+    /// Returns: `Result<Self, builtins.ValueError>`
+    #[pyo3(text_signature = "($self, volume, /)")]
+    fn volume(mut slf: PyRefMut<Self>, volume: u16) -> PyResult<PyRefMut<Self>> {
+        validate_volume("volume", volume)?;
+        slf.volume = Some(volume);
+        Ok(slf)
+    }
+
     /// Sets if the current playing track should be replaced with this new one.
     ///
     /// Positional Arguments:
@@ -349,4 +701,142 @@ impl PlayBuilder {
         slf.builder.finish = finish;
         slf
     }
+
+    /// Sets the time the track will finish at to a number of seconds before the end of the track,
+    /// to skip trailing silence or outros. It won't finish before the start time.
+    ///
+    /// If the track doesn't have info, the length is unknown, so this only logs a warning.
+    ///
+    /// Positional Arguments:
+    /// - `secs` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, secs, /)")]
+    fn trim_end_secs(slf: PyRefMut<Self>, secs: u64) -> PyRefMut<Self> {
+        Self::trim_end_millis(slf, Duration::from_secs(secs).as_millis() as u64)
+    }
+
+    /// Sets the time the track will finish at to a number of milliseconds before the end of the
+    /// track. It won't finish before the start time.
+    ///
+    /// If the track doesn't have info, the length is unknown, so this only logs a warning.
+    ///
+    /// Positional Arguments:
+    /// - `millis` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Self`
+    #[pyo3(text_signature = "($self, millis, /)")]
+    fn trim_end_millis(mut slf: PyRefMut<Self>, millis: u64) -> PyRefMut<Self> {
+        let length = slf.builder.track.info.as_ref().map(|info| info.length);
+
+        match length {
+            Some(length) => {
+                slf.builder.finish = length.saturating_sub(millis).max(slf.builder.start);
+            }
+            None => warn!("Unable to trim the end of a track without info."),
+        }
+
+        slf
+    }
+}
+
+/// Discord snowflakes store the creation timestamp on the bits after the 22nd, so any ID lower
+/// than that can't be a real ID.
+fn validate_snowflake(name: &str, id: u64) -> PyResult<()> {
+    if id >> 22 == 0 {
+        return Err(error::ValueError::new_err(format!(
+            "{} must be a discord snowflake, got {}",
+            name, id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Adds the track to the node queue without starting a queue loop, and returns its index.
+pub async fn add_to_node(
+    lava_client: &LavalinkClient,
+    guild_id: u64,
+    track_queue: LavaTrackQueue,
+) -> PyResult<usize> {
+    let nodes = lava_client.nodes().await;
+    let mut node = nodes.get_mut(&guild_id).ok_or_else(|| {
+        error::NoSessionPresent::new_err(LavalinkError::NoSessionPresent.to_string())
+    })?;
+
+    node.queue.push(track_queue);
+
+    Ok(node.queue.len() - 1)
+}
+
+/// Remembers the volume to set when the track starts, for `PlayBuilder.volume()` on queued tracks.
+fn add_pending_volume(data: &LavalinkData, guild_id: u64, track: String, volume: u16) {
+    data.pending_volumes
+        .lock()
+        .entry(guild_id)
+        .or_default()
+        .push((track, volume));
+}
+
+/// Adds the track to the node queue like `PlayParameters::queue()` does, starting the queue loop if
+/// it's not running, and returns its index.
+///
+/// If the loop is running, the track is pushed once and the index is taken under the same lock as
+/// the push, otherwise it's queued through lavalink-rs, as that's the only way to start the loop.
+pub async fn queue_with_loop(
+    lava_client: &LavalinkClient,
+    guild_id: u64,
+    builder: PlayParameters,
+    track_queue: LavaTrackQueue,
+) -> PyResult<usize> {
+    if lava_client.loops().await.contains(&guild_id) {
+        let position = add_to_node(lava_client, guild_id, track_queue.clone()).await?;
+
+        if lava_client.loops().await.contains(&guild_id) {
+            return Ok(position);
+        }
+
+        // The loop stopped before it could see the track, so it's taken back out to be queued
+        // through lavalink-rs, which starts a new loop.
+        remove_from_node(lava_client, guild_id, &track_queue).await;
+    }
+
+    builder.queue().await.map_err(error::queue_error)?;
+
+    let nodes = lava_client.nodes().await;
+    let node = nodes.get(&guild_id).ok_or_else(|| {
+        error::NoSessionPresent::new_err(LavalinkError::NoSessionPresent.to_string())
+    })?;
+
+    Ok(node
+        .queue
+        .iter()
+        .rposition(|i| is_same_track_queue(i, &track_queue))
+        .unwrap_or(0))
+}
+
+/// Removes the last copy of the track from the node queue, undoing `add_to_node()`.
+async fn remove_from_node(
+    lava_client: &LavalinkClient,
+    guild_id: u64,
+    track_queue: &LavaTrackQueue,
+) {
+    let nodes = lava_client.nodes().await;
+
+    if let Some(mut node) = nodes.get_mut(&guild_id) {
+        if let Some(index) = node
+            .queue
+            .iter()
+            .rposition(|i| is_same_track_queue(i, track_queue))
+        {
+            node.queue.remove(index);
+        }
+    }
+}
+
+fn is_same_track_queue(a: &LavaTrackQueue, b: &LavaTrackQueue) -> bool {
+    a.track.track == b.track.track
+        && a.start_time == b.start_time
+        && a.end_time == b.end_time
+        && a.requester.map(|id| id.0) == b.requester.map(|id| id.0)
 }
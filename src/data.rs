@@ -0,0 +1,430 @@
+use crate::model::TrackStart;
+use crate::sources::Source;
+
+use lavalink_rs::model::{Stats, TrackQueue as LavaTrackQueue, Tracks as LavaTracks};
+use parking_lot::Mutex;
+use pyo3::prelude::*;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+/// Settings configured with `LavalinkBuilder` that are handled by lavasnek_rs rather than by the
+/// lavalink-rs client.
+#[derive(Clone, Debug)]
+pub struct LavalinkConfig {
+    pub voice_timeout: Duration,
+    pub rest_timeout: Option<Duration>,
+    pub rest_retries: RestRetries,
+    pub idle_timeout: Option<Duration>,
+    pub idle_disconnect: bool,
+    pub error_handler: Option<PyObject>,
+    pub manage_queue: bool,
+    pub history_size: usize,
+    pub search_sources: Vec<&'static Source>,
+    pub search_cache: Option<SearchCacheConfig>,
+}
+
+impl Default for LavalinkConfig {
+    fn default() -> Self {
+        Self {
+            voice_timeout: Duration::from_secs(5),
+            rest_timeout: None,
+            rest_retries: RestRetries::default(),
+            idle_timeout: None,
+            idle_disconnect: false,
+            error_handler: None,
+            manage_queue: true,
+            history_size: 50,
+            search_sources: vec![],
+            search_cache: None,
+        }
+    }
+}
+
+/// Access to the REST API of the Lavalink server, for the endpoints lavalink-rs doesn't wrap.
+#[derive(Clone, Debug, Default)]
+pub struct LavalinkRest {
+    pub client: reqwest::Client,
+    pub uri: String,
+    pub password: String,
+}
+
+impl LavalinkRest {
+    pub fn new(host: &str, port: u16, is_ssl: bool, password: String) -> Self {
+        let scheme = if is_ssl { "https" } else { "http" };
+
+        Self {
+            client: reqwest::Client::new(),
+            uri: format!("{}://{}:{}", scheme, host, port),
+            password,
+        }
+    }
+
+    pub fn get(&self, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .get(format!("{}{}", self.uri, path))
+            .header("Authorization", &self.password)
+    }
+}
+
+/// Data shared between every `Lavalink` instance of the same client and its event handler.
+#[derive(Debug, Default)]
+pub struct LavalinkData {
+    pub config: LavalinkConfig,
+    pub rest: LavalinkRest,
+    /// The last stats sent by the Lavalink server.
+    pub stats: Mutex<Option<Stats>>,
+    /// When the current track of each guild started, in milliseconds since the unix epoch.
+    pub playing_since: Mutex<HashMap<u64, u64>>,
+    /// The last position of the track of each guild sent by the `player_update` event, in
+    /// milliseconds.
+    pub positions: Mutex<HashMap<u64, i64>>,
+    /// The `PlayerUpdate.state_ping_ms` of the last `player_update` event of each guild.
+    pub latencies: Mutex<HashMap<u64, i64>>,
+    /// The volume fade running on each guild.
+    pub fades: GuildTasks,
+    /// The idle timer running on each guild.
+    pub idle_timers: GuildTasks,
+    /// The event loop the events are run on, updated with `Lavalink.set_event_loop()`.
+    pub event_loop: Mutex<Option<PyObject>>,
+    /// The `Lavalink.wait_for_track_start()` calls waiting on each guild.
+    pub track_start_waiters: Mutex<HashMap<u64, Vec<oneshot::Sender<TrackStart>>>>,
+    /// The queued track that the last `track_start` event of each guild started.
+    pub current_tracks: Mutex<HashMap<u64, LavaTrackQueue>>,
+    /// The last finished or skipped tracks of each guild, for `Lavalink.previous()`.
+    pub previous_tracks: TrackHistory,
+    /// The tracks that finished playing naturally on each guild, for `Lavalink.get_history()`.
+    pub history: TrackHistory,
+    /// The track that last started on each guild, and how many times in a row it started again
+    /// after itself, for `Lavalink.loop_count()`.
+    pub loop_counts: Mutex<HashMap<u64, (String, u32)>>,
+    /// The volumes set with `PlayBuilder.volume()` on queued tracks of each guild, applied when the
+    /// track starts, by track.
+    pub pending_volumes: Mutex<HashMap<u64, Vec<(String, u16)>>>,
+    /// The guilds with autoplay enabled by `Lavalink.autoplay()`.
+    pub autoplay: Mutex<HashSet<u64>>,
+    /// The results of the last searches, if enabled with `LavalinkBuilder.set_search_cache()`.
+    pub search_cache: SearchCache,
+    /// The guilds paused by `Lavalink.set_pause_all()`, so resuming them all leaves alone the
+    /// players that were paused before.
+    pub paused_by_all: Mutex<HashSet<u64>>,
+}
+
+impl LavalinkData {
+    pub fn new(config: LavalinkConfig, rest: LavalinkRest, event_loop: PyObject) -> Self {
+        Self {
+            config,
+            rest,
+            event_loop: Mutex::new(Some(event_loop)),
+            ..Default::default()
+        }
+    }
+}
+
+/// How many times the REST requests that are safe to repeat are retried when they fail, and how
+/// long to wait before the first retry. The wait doubles on every retry.
+#[derive(Clone, Copy, Debug)]
+pub struct RestRetries {
+    pub count: u32,
+    pub backoff: Duration,
+}
+
+impl Default for RestRetries {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+/// How many search results are cached, and for how long.
+#[derive(Clone, Copy, Debug)]
+pub struct SearchCacheConfig {
+    pub size: usize,
+    pub ttl: Duration,
+}
+
+/// The results of the last searches, keyed by the normalized query. When it's full, the least
+/// recently used result is removed first.
+#[derive(Debug, Default)]
+pub struct SearchCache {
+    entries: Mutex<SearchCacheEntries>,
+}
+
+#[derive(Debug, Default)]
+struct SearchCacheEntries {
+    tracks: HashMap<String, (Instant, LavaTracks)>,
+    /// The keys of `tracks`, least recently used first.
+    order: VecDeque<String>,
+}
+
+impl SearchCache {
+    /// Lowercases the query and collapses its whitespace, so searches that only differ in those
+    /// share the same result.
+    pub fn key(prefix: &str, query: &str) -> String {
+        let query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        format!("{}:{}", prefix, query.to_lowercase())
+    }
+
+    /// Returns the cached result, if it's younger than the TTL.
+    pub fn get(&self, config: &SearchCacheConfig, key: &str) -> Option<LavaTracks> {
+        let mut entries = self.entries.lock();
+
+        let tracks = match entries.tracks.get(key) {
+            Some((cached_at, tracks)) if cached_at.elapsed() < config.ttl => tracks.clone(),
+            Some(_) => {
+                entries.tracks.remove(key);
+                entries.order.retain(|i| i != key);
+                return None;
+            }
+            None => return None,
+        };
+
+        entries.order.retain(|i| i != key);
+        entries.order.push_back(key.to_string());
+
+        Some(tracks)
+    }
+
+    /// Caches a result, removing the least recently used ones if there are more than the size.
+    pub fn insert(&self, config: &SearchCacheConfig, key: String, tracks: LavaTracks) {
+        let mut entries = self.entries.lock();
+
+        entries.order.retain(|i| *i != key);
+        entries.order.push_back(key.clone());
+        entries.tracks.insert(key, (Instant::now(), tracks));
+
+        while entries.order.len() > config.size {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.tracks.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// A list of tracks per guild that keeps only the newest ones.
+#[derive(Debug, Default)]
+pub struct TrackHistory {
+    tracks: Mutex<HashMap<u64, VecDeque<LavaTrackQueue>>>,
+}
+
+impl TrackHistory {
+    /// Adds a track as the newest one, removing the oldest ones if there are more than `limit`.
+    pub fn push(&self, guild_id: u64, track_queue: LavaTrackQueue, limit: usize) {
+        let mut tracks = self.tracks.lock();
+        let guild_tracks = tracks.entry(guild_id).or_default();
+
+        guild_tracks.push_back(track_queue);
+
+        while guild_tracks.len() > limit {
+            guild_tracks.pop_front();
+        }
+    }
+
+    /// Removes and returns the newest track.
+    pub fn pop(&self, guild_id: u64) -> Option<LavaTrackQueue> {
+        self.tracks
+            .lock()
+            .get_mut(&guild_id)
+            .and_then(|guild_tracks| guild_tracks.pop_back())
+    }
+
+    /// Returns the tracks, newest first.
+    pub fn get(&self, guild_id: u64) -> Vec<LavaTrackQueue> {
+        self.tracks
+            .lock()
+            .get(&guild_id)
+            .map_or_else(Vec::new, |guild_tracks| {
+                guild_tracks.iter().rev().cloned().collect()
+            })
+    }
+
+    pub fn clear(&self, guild_id: u64) {
+        self.tracks.lock().remove(&guild_id);
+    }
+}
+
+/// Keeps track of a background task per guild, so starting a new one cancels the previous one.
+///
+/// The tasks are not aborted, they are expected to check `is_current()` before doing anything.
+#[derive(Debug, Default)]
+pub struct GuildTasks {
+    tasks: Mutex<HashMap<u64, u64>>,
+    counter: AtomicU64,
+}
+
+impl GuildTasks {
+    /// Registers a new task for the guild, cancelling the previous one, and returns its ID.
+    pub fn start(&self, guild_id: u64) -> u64 {
+        let task_id = self.counter.fetch_add(1, Ordering::Relaxed);
+        self.tasks.lock().insert(guild_id, task_id);
+
+        task_id
+    }
+
+    /// Returns `false` if the task was cancelled, or a newer one started on the guild.
+    pub fn is_current(&self, guild_id: u64, task_id: u64) -> bool {
+        self.tasks.lock().get(&guild_id) == Some(&task_id)
+    }
+
+    /// Removes the task, if it's still the current one of the guild.
+    pub fn finish(&self, guild_id: u64, task_id: u64) {
+        let mut tasks = self.tasks.lock();
+
+        if tasks.get(&guild_id) == Some(&task_id) {
+            tasks.remove(&guild_id);
+        }
+    }
+
+    /// Cancels the current task of the guild.
+    pub fn cancel(&self, guild_id: u64) {
+        self.tasks.lock().remove(&guild_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lavalink_rs::model::Track as LavaTrack;
+
+    fn tracks(track: &str) -> LavaTracks {
+        LavaTracks {
+            load_type: "SEARCH_RESULT".to_string(),
+            playlist_info: None,
+            tracks: vec![LavaTrack {
+                track: track.to_string(),
+                info: None,
+            }],
+        }
+    }
+
+    fn track_queue(track: &str) -> LavaTrackQueue {
+        LavaTrackQueue {
+            track: LavaTrack {
+                track: track.to_string(),
+                info: None,
+            },
+            start_time: 0,
+            end_time: None,
+            requester: None,
+        }
+    }
+
+    fn history_tracks(history: &TrackHistory, guild_id: u64) -> Vec<String> {
+        history
+            .get(guild_id)
+            .into_iter()
+            .map(|track_queue| track_queue.track.track)
+            .collect()
+    }
+
+    fn cached_track(cache: &SearchCache, config: &SearchCacheConfig, key: &str) -> Option<String> {
+        cache
+            .get(config, key)
+            .map(|tracks| tracks.tracks[0].track.clone())
+    }
+
+    #[test]
+    fn search_cache_key_is_normalized() {
+        assert_eq!(
+            SearchCache::key("ytsearch", "  Never   Gonna\tGive "),
+            SearchCache::key("ytsearch", "never gonna give"),
+        );
+        assert_ne!(
+            SearchCache::key("ytsearch", "never gonna give"),
+            SearchCache::key("scsearch", "never gonna give"),
+        );
+    }
+
+    #[test]
+    fn search_cache_evicts_least_recently_used() {
+        let config = SearchCacheConfig {
+            size: 2,
+            ttl: Duration::from_secs(60),
+        };
+        let cache = SearchCache::default();
+
+        cache.insert(&config, "a".to_string(), tracks("a"));
+        cache.insert(&config, "b".to_string(), tracks("b"));
+
+        // Using `a` makes `b` the least recently used.
+        assert_eq!(cached_track(&cache, &config, "a").as_deref(), Some("a"));
+
+        cache.insert(&config, "c".to_string(), tracks("c"));
+
+        assert_eq!(cached_track(&cache, &config, "a").as_deref(), Some("a"));
+        assert_eq!(cached_track(&cache, &config, "b"), None);
+        assert_eq!(cached_track(&cache, &config, "c").as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn search_cache_replaces_existing_key() {
+        let config = SearchCacheConfig {
+            size: 2,
+            ttl: Duration::from_secs(60),
+        };
+        let cache = SearchCache::default();
+
+        cache.insert(&config, "a".to_string(), tracks("old"));
+        cache.insert(&config, "a".to_string(), tracks("new"));
+        cache.insert(&config, "b".to_string(), tracks("b"));
+
+        assert_eq!(cached_track(&cache, &config, "a").as_deref(), Some("new"));
+        assert_eq!(cached_track(&cache, &config, "b").as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn search_cache_expires_after_ttl() {
+        let config = SearchCacheConfig {
+            size: 2,
+            ttl: Duration::from_secs(0),
+        };
+        let cache = SearchCache::default();
+
+        cache.insert(&config, "a".to_string(), tracks("a"));
+
+        assert_eq!(cached_track(&cache, &config, "a"), None);
+        assert!(cache.entries.lock().tracks.is_empty());
+        assert!(cache.entries.lock().order.is_empty());
+    }
+
+    #[test]
+    fn track_history_keeps_newest_up_to_limit() {
+        let history = TrackHistory::default();
+
+        for track in &["a", "b", "c", "d"] {
+            history.push(1, track_queue(track), 3);
+        }
+
+        assert_eq!(history_tracks(&history, 1), vec!["d", "c", "b"]);
+    }
+
+    #[test]
+    fn track_history_pops_newest_first() {
+        let history = TrackHistory::default();
+
+        history.push(1, track_queue("a"), 3);
+        history.push(1, track_queue("b"), 3);
+
+        assert_eq!(history.pop(1).map(|i| i.track.track).as_deref(), Some("b"));
+        assert_eq!(history.pop(1).map(|i| i.track.track).as_deref(), Some("a"));
+        assert!(history.pop(1).is_none());
+    }
+
+    #[test]
+    fn track_history_is_per_guild() {
+        let history = TrackHistory::default();
+
+        history.push(1, track_queue("a"), 3);
+        history.push(2, track_queue("b"), 3);
+        history.clear(1);
+
+        assert!(history_tracks(&history, 1).is_empty());
+        assert_eq!(history_tracks(&history, 2), vec!["b"]);
+    }
+}
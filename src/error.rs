@@ -1,11 +1,37 @@
+use lavalink_rs::error::LavalinkError;
 use pyo3::exceptions::PyException;
+use pyo3::PyErr;
 
 pyo3::import_exception!(builtins, ValueError);
 pyo3::import_exception!(builtins, ConnectionError);
 pyo3::import_exception!(builtins, TimeoutError);
 pyo3::import_exception!(builtins, TypeError);
-pyo3::import_exception!(builtins, Exception);
-pyo3::import_exception!(builtins, NameError);
+pyo3::import_exception!(builtins, IndexError);
+pyo3::import_exception!(builtins, RuntimeError);
 pyo3::import_exception!(ipaddress, AddressValueError);
 pyo3::create_exception!(lavasnek_rs, NoSessionPresent, PyException);
 pyo3::create_exception!(lavasnek_rs, NetworkError, PyException);
+pyo3::create_exception!(lavasnek_rs, PlayerError, NetworkError);
+pyo3::create_exception!(lavasnek_rs, LoadFailedError, PyException);
+pyo3::create_exception!(lavasnek_rs, NoConnectionInfo, TimeoutError);
+pyo3::create_exception!(lavasnek_rs, QueueError, PyException);
+
+/// Maps the errors of player operations, raising `PlayerError` when Lavalink can't act on the
+/// player, and `NetworkError` for everything else.
+pub fn player_error(e: LavalinkError) -> PyErr {
+    match e {
+        LavalinkError::NoSessionPresent => PlayerError::new_err(e.to_string()),
+        _ => NetworkError::new_err(e.to_string()),
+    }
+}
+
+/// Maps the errors of adding a track to the queue, raising `NoSessionPresent` if the guild has no
+/// Node, `NetworkError` if the track couldn't be sent to Lavalink, and `QueueError` for everything
+/// else.
+pub fn queue_error(e: LavalinkError) -> PyErr {
+    match e {
+        LavalinkError::NoSessionPresent => NoSessionPresent::new_err(e.to_string()),
+        LavalinkError::ErrorWebsocketPayload(_) => NetworkError::new_err(e.to_string()),
+        _ => QueueError::new_err(e.to_string()),
+    }
+}
@@ -1,9 +1,17 @@
+use crate::builders::add_to_node;
+use crate::data::LavalinkData;
 use crate::error;
 use crate::model;
+use crate::sources;
 use crate::Lavalink;
 
 use pyo3::prelude::*;
 
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tokio::time::sleep;
+
 use lavalink_rs::{
     async_trait, gateway::LavalinkEventHandler as LavalinkEventHandlerTrait, model::*,
     LavalinkClient,
@@ -13,13 +21,20 @@ use lavalink_rs::{
 #[derive(Clone)]
 /// The lavalink event handler. This is a trait, so it defines the structure a class should have.
 ///
-/// Make a class with the methods and signatures this class defines, and add that class to
-/// `LavalinkBuilder.build()`
+/// Make a class with the methods and signatures this class defines, and add an instance of that
+/// class to `LavalinkBuilder.build()`. The same instance is used for every event, so any state set
+/// on it, like a reference to the bot, is available on `self`. If the class itself is passed, it
+/// gets instantiated once without arguments.
 ///
 /// If code inside any of the event raises an error, the traceback will be printed to stderr, and
 /// the variables `sys.last_type`, `sys.last_value` and `sys.last_traceback` will be set to the type, value
 /// and traceback of the printed exception respectively.
 ///
+/// Use `LavalinkBuilder.set_error_handler()` to handle those exceptions instead.
+///
+/// Missing events are ignored, but a `RuntimeWarning` is emitted if an event can't be called, like
+/// if it's not async, or if a method looks like a misspelled event, like `track_strat`.
+///
 /// Some examples:
 ///
 /// ```py
@@ -32,6 +47,18 @@ use lavalink_rs::{
 /// ```
 ///
 /// ```py
+/// # Sharing state with the events
+/// class EventHandler:
+///     def __init__(self, bot):
+///         self.bot = bot
+///
+///     async def track_start(self, lava_client, event):
+///         await self.bot.update_presence(event.track)
+///
+/// lavalink_client = await client_builder.build(EventHandler(bot))
+/// ```
+///
+/// ```py
 /// # No events
 /// class EventHandler:
 ///     pass
@@ -58,29 +85,156 @@ use lavalink_rs::{
 ///         print(event)
 ///     async def player_destroyed(self, lava_client, event):
 ///         print(event)
+///     async def idle_timeout(self, lava_client, event):
+///         print(event)
 ///
 /// lavalink_client = await client_builder.build(EventHandler)
 /// ```
 pub struct LavalinkEventHandler {
     pub inner: PyObject,
-    pub current_loop: PyObject,
+    pub data: Arc<LavalinkData>,
 }
 
 #[async_trait]
 impl LavalinkEventHandlerTrait for LavalinkEventHandler {
     async fn stats(&self, client: LavalinkClient, event: Stats) {
+        *self.data.stats.lock() = Some(event.clone());
+
         let event = model::Stats { inner: event };
         call_event(self, client, event, "stats");
     }
     async fn player_update(&self, client: LavalinkClient, event: PlayerUpdate) {
-        let event = model::PlayerUpdate { inner: event };
+        self.data
+            .positions
+            .lock()
+            .insert(event.guild_id.0, event.state.position);
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis() as i64);
+        let ping_ms = now - event.state.time;
+        self.data.latencies.lock().insert(event.guild_id.0, ping_ms);
+
+        let event = model::PlayerUpdate {
+            inner: event,
+            ping_ms,
+        };
         call_event(self, client, event, "player_update");
     }
     async fn track_start(&self, client: LavalinkClient, event: TrackStart) {
-        let event = model::TrackStart { inner: event };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis() as u64);
+        self.data.playing_since.lock().insert(event.guild_id.0, now);
+        self.data.idle_timers.cancel(event.guild_id.0);
+
+        self.data
+            .loop_counts
+            .lock()
+            .entry(event.guild_id.0)
+            .and_modify(|(track, count)| {
+                if *track == event.track {
+                    *count += 1;
+                } else {
+                    *track = event.track.clone();
+                    *count = 0;
+                }
+            })
+            .or_insert_with(|| (event.track.clone(), 0));
+
+        let track_queue = client
+            .nodes()
+            .await
+            .get(&event.guild_id.0)
+            .and_then(|node| {
+                node.now_playing
+                    .iter()
+                    .chain(node.queue.iter())
+                    .find(|track_queue| track_queue.track.track == event.track)
+                    .cloned()
+            });
+
+        let info = track_queue
+            .as_ref()
+            .and_then(|track_queue| track_queue.track.info.clone());
+
+        let pending_volume = self
+            .data
+            .pending_volumes
+            .lock()
+            .get_mut(&event.guild_id.0)
+            .and_then(|volumes| {
+                let index = volumes
+                    .iter()
+                    .position(|(track, _)| *track == event.track)?;
+                Some(volumes.remove(index).1)
+            });
+
+        if let Some(volume) = pending_volume {
+            if let Err(e) = client.volume(event.guild_id.0, volume).await {
+                warn!("Failed to set the volume of the track that started: {}", e);
+            }
+        }
+
+        match track_queue {
+            Some(track_queue) => {
+                self.data
+                    .current_tracks
+                    .lock()
+                    .insert(event.guild_id.0, track_queue);
+            }
+            None => {
+                self.data.current_tracks.lock().remove(&event.guild_id.0);
+            }
+        }
+
+        let event = model::TrackStart { inner: event, info };
+
+        let waiters = self
+            .data
+            .track_start_waiters
+            .lock()
+            .remove(&event.inner.guild_id.0);
+        for waiter in waiters.into_iter().flatten() {
+            let _ = waiter.send(event.clone());
+        }
+
         call_event(self, client, event, "track_start");
     }
     async fn track_finish(&self, client: LavalinkClient, event: TrackFinish) {
+        self.data.playing_since.lock().remove(&event.guild_id.0);
+        self.data.positions.lock().remove(&event.guild_id.0);
+        start_idle_timer(self, client.clone(), event.guild_id.0);
+
+        let finished = self
+            .data
+            .current_tracks
+            .lock()
+            .remove(&event.guild_id.0)
+            .filter(|t| t.track.track == event.track);
+
+        if event.reason == "FINISHED" {
+            let seed = Track {
+                track: event.track.clone(),
+                info: finished.as_ref().and_then(|t| t.track.info.clone()),
+            };
+            start_autoplay(self, client.clone(), event.guild_id.0, seed);
+        }
+
+        if let Some(track_queue) = finished {
+            if event.reason == "FINISHED" {
+                self.data.history.push(
+                    event.guild_id.0,
+                    track_queue.clone(),
+                    self.data.config.history_size,
+                );
+            }
+
+            self.data
+                .previous_tracks
+                .push(event.guild_id.0, track_queue, PREVIOUS_TRACKS_LIMIT);
+        }
+
         let event = model::TrackFinish { inner: event };
         call_event(self, client, event, "track_finish");
     }
@@ -97,6 +251,15 @@ impl LavalinkEventHandlerTrait for LavalinkEventHandler {
         call_event(self, client, event, "websocket_closed");
     }
     async fn player_destroyed(&self, client: LavalinkClient, event: PlayerDestroyed) {
+        self.data.playing_since.lock().remove(&event.guild_id.0);
+        self.data.positions.lock().remove(&event.guild_id.0);
+        self.data.latencies.lock().remove(&event.guild_id.0);
+        self.data.current_tracks.lock().remove(&event.guild_id.0);
+        self.data.loop_counts.lock().remove(&event.guild_id.0);
+        self.data.paused_by_all.lock().remove(&event.guild_id.0);
+        self.data.pending_volumes.lock().remove(&event.guild_id.0);
+        self.data.idle_timers.cancel(event.guild_id.0);
+
         let event = model::PlayerDestroyed { inner: event };
         call_event(self, client, event, "player_destroyed");
     }
@@ -176,6 +339,182 @@ impl LavalinkEventHandler {
     ///
     /// Returns: `Future<None>`
     fn player_destroyed(&self) {}
+    #[pyo3(text_signature = "($self, client, event, /)")]
+    /// Event that triggers when nothing played on a guild for the time set with
+    /// `LavalinkBuilder.set_idle_timeout_secs()`, after the last track finished.
+    ///
+    /// Positional Arguments:
+    /// - `client` : `Lavalink`
+    /// - `event` : `IdleTimeout`
+    ///
+    /// Returns: `Future<None>`
+    fn idle_timeout(&self) {}
+    #[pyo3(text_signature = "($self, client, event, /)")]
+    /// Event that triggers when a related track was queued because the queue ran out, with
+    /// autoplay enabled by `Lavalink.autoplay()`.
+    ///
+    /// Positional Arguments:
+    /// - `client` : `Lavalink`
+    /// - `event` : `AutoplayTrack`
+    ///
+    /// Returns: `Future<None>`
+    fn autoplay_track(&self) {}
+}
+
+/// Starts the idle timer of the guild, if an idle timeout is configured.
+///
+/// The timer is restarted by every `track_finish`, and cancelled by `track_start` and
+/// `player_destroyed`, so it only runs out if nothing started playing after the last track.
+fn start_idle_timer(handler: &LavalinkEventHandler, client: LavalinkClient, guild_id: u64) {
+    let idle_timeout = match handler.data.config.idle_timeout {
+        Some(idle_timeout) => idle_timeout,
+        None => return,
+    };
+
+    let handler = handler.clone();
+    let timer_id = handler.data.idle_timers.start(guild_id);
+
+    tokio::spawn(async move {
+        sleep(idle_timeout).await;
+
+        if !handler.data.idle_timers.is_current(guild_id, timer_id) {
+            return;
+        }
+
+        handler.data.idle_timers.finish(guild_id, timer_id);
+
+        let mut disconnected = false;
+
+        if handler.data.config.idle_disconnect {
+            let voice_timeout = handler.data.config.voice_timeout;
+
+            match crate::teardown_guild(&client, guild_id, voice_timeout).await {
+                Ok(()) => disconnected = true,
+                Err(e) => Python::with_gil(|py| e.print_and_set_sys_last_vars(py)),
+            }
+        }
+
+        let event = model::IdleTimeout {
+            guild_id,
+            disconnected,
+        };
+        call_event(&handler, client, event, "idle_timeout");
+    });
+}
+
+/// Queues a track related to the one that finished, if autoplay is enabled on the guild and there
+/// is nothing else in the queue.
+fn start_autoplay(
+    handler: &LavalinkEventHandler,
+    client: LavalinkClient,
+    guild_id: u64,
+    seed: Track,
+) {
+    if !handler.data.autoplay.lock().contains(&guild_id) {
+        return;
+    }
+
+    let handler = handler.clone();
+
+    tokio::spawn(async move {
+        let is_queue_empty = client
+            .nodes()
+            .await
+            .get(&guild_id)
+            .map_or(false, |node| node.queue.is_empty());
+
+        if !is_queue_empty {
+            return;
+        }
+
+        match queue_related_track(&handler.data, &client, guild_id, seed).await {
+            Ok(Some(track)) => {
+                let event = model::AutoplayTrack { guild_id, track };
+                call_event(&handler, client, event, "autoplay_track");
+            }
+            Ok(None) => debug!("No related track found to autoplay on {}", guild_id),
+            Err(e) => Python::with_gil(|py| e.print_and_set_sys_last_vars(py)),
+        }
+    });
+}
+
+/// Finds a track related to `seed` that didn't play recently, and queues it.
+///
+/// Spotify tracks use the LavaSrc recommendations, YouTube tracks use the YouTube mix of the track,
+/// and tracks from other sources are searched on YouTube first to use the mix of the result.
+async fn queue_related_track(
+    data: &LavalinkData,
+    client: &LavalinkClient,
+    guild_id: u64,
+    seed: Track,
+) -> PyResult<Option<Track>> {
+    let rest_timeout = data.config.rest_timeout;
+
+    let info = match seed.info {
+        Some(info) => info,
+        None => {
+            crate::rest_with_timeout(rest_timeout, client.decode_track(seed.track.clone())).await?
+        }
+    };
+
+    let query = match sources::source_for_url(&info.uri) {
+        Some(source) if source.name == "spotify" => {
+            crate::ensure_plugin_source(&data.rest, rest_timeout, source).await?;
+            format!("sprec:seed_tracks={}", info.identifier)
+        }
+        Some(source) if source.name == "youtube" => youtube_mix_url(&info.identifier),
+        _ => {
+            let search = format!("ytsearch:{} {}", info.author, info.title);
+            let results =
+                crate::rest_with_timeout(rest_timeout, client.get_tracks(&search)).await?;
+
+            match results.tracks.first().and_then(|track| track.info.as_ref()) {
+                Some(info) => youtube_mix_url(&info.identifier),
+                None => return Ok(None),
+            }
+        }
+    };
+
+    let related = crate::rest_with_timeout(rest_timeout, client.get_tracks(&query)).await?;
+
+    let recent = data.previous_tracks.get(guild_id);
+    let track = related.tracks.into_iter().find(|track| {
+        let is_seed = track.track == seed.track
+            || track
+                .info
+                .as_ref()
+                .map_or(false, |i| i.identifier == info.identifier);
+        let is_recent = recent.iter().any(|t| t.track.track == track.track);
+
+        !is_seed && !is_recent
+    });
+
+    let track = match track {
+        Some(track) => track,
+        None => return Ok(None),
+    };
+
+    if data.config.manage_queue {
+        client
+            .play(guild_id, track.clone())
+            .queue()
+            .await
+            .map_err(error::queue_error)?;
+    } else {
+        let track_queue = TrackQueue {
+            track: track.clone(),
+            start_time: 0,
+            end_time: None,
+            requester: None,
+        };
+        add_to_node(client, guild_id, track_queue).await?;
+    }
+
+    Ok(Some(track))
+}
+
+fn youtube_mix_url(identifier: &str) -> String {
+    format!("https://www.youtube.com/watch?v={0}&list=RD{0}", identifier)
 }
 
 fn call_event<T: Send + Sync + pyo3::IntoPy<PyObject> + 'static>(
@@ -184,11 +523,28 @@ fn call_event<T: Send + Sync + pyo3::IntoPy<PyObject> + 'static>(
     event: T,
     name: &'static str,
 ) {
-    let slf1 = handler.clone();
     let slf2 = handler.clone();
 
     Python::with_gil(|py| {
-        let current_loop = slf1.current_loop.as_ref(py);
+        let current_loop = match handler.data.event_loop.lock().as_ref() {
+            Some(current_loop) => current_loop.clone_ref(py),
+            None => return,
+        };
+        let current_loop = current_loop.as_ref(py);
+
+        let is_closed = current_loop
+            .call_method0("is_closed")
+            .and_then(|is_closed| is_closed.is_true())
+            .unwrap_or(true);
+
+        if is_closed {
+            error!(
+                "The event loop of the event handler is closed, so the `{}` event was dropped. \
+                 Use `Lavalink.set_event_loop()` to run the events on the new event loop.",
+                name
+            );
+            return;
+        }
 
         pyo3_asyncio::tokio::future_into_py_with_locals(
             py,
@@ -196,24 +552,46 @@ fn call_event<T: Send + Sync + pyo3::IntoPy<PyObject> + 'static>(
             async move {
                 let future = Python::with_gil(|py| {
                     let py_event_handler = slf2.inner.as_ref(py);
-                    let coro_result = py_event_handler.call_method(
-                        name,
-                        (Lavalink { lava: client }, event),
-                        None,
-                    );
-
-                    if let Ok(coro) = coro_result {
-                        pyo3_asyncio::tokio::into_future(coro)
-                    } else {
-                        Err(error::NameError::new_err("Undefined event"))
+
+                    // Events are optional, so a missing method is not an error.
+                    if !py_event_handler.hasattr(name).unwrap_or(false) {
+                        return None;
+                    }
+
+                    let coro_result = py_event_handler
+                        .call_method(
+                            name,
+                            (
+                                Lavalink {
+                                    lava: client,
+                                    data: slf2.data.clone(),
+                                },
+                                event,
+                            ),
+                            None,
+                        )
+                        .and_then(pyo3_asyncio::tokio::into_future);
+
+                    match coro_result {
+                        Ok(coro) => Some(coro),
+                        Err(e) => {
+                            warn_event(
+                                py,
+                                &format!(
+                                    "The `{}` event handler could not be called, it must be an \
+                                     async method that takes the client and the event: {}",
+                                    name, e
+                                ),
+                            );
+
+                            None
+                        }
                     }
                 });
 
-                if let Ok(f) = future {
+                if let Some(f) = future {
                     if let Err(e) = f.await {
-                        Python::with_gil(|py| {
-                            e.print_and_set_sys_last_vars(py);
-                        });
+                        handle_event_error(&slf2.data, e, name).await;
                     }
                 }
 
@@ -223,3 +601,121 @@ fn call_event<T: Send + Sync + pyo3::IntoPy<PyObject> + 'static>(
         .unwrap();
     });
 }
+
+/// Sends the exception raised by an event to the error handler set with
+/// `LavalinkBuilder.set_error_handler()`, or prints it if there's none.
+async fn handle_event_error(data: &LavalinkData, e: PyErr, name: &'static str) {
+    let error_handler = match &data.config.error_handler {
+        Some(error_handler) => error_handler.clone(),
+        None => {
+            Python::with_gil(|py| e.print_and_set_sys_last_vars(py));
+            return;
+        }
+    };
+
+    let result = Python::with_gil(|py| {
+        let result = error_handler.call1(py, (e.clone_ref(py).into_py(py), name))?;
+        let result = result.as_ref(py);
+
+        let is_awaitable = py
+            .import("inspect")?
+            .call_method1("isawaitable", (result,))?
+            .is_true()?;
+
+        if is_awaitable {
+            pyo3_asyncio::tokio::into_future(result).map(Some)
+        } else {
+            Ok(None)
+        }
+    });
+
+    let result = match result {
+        Ok(Some(future)) => future.await.map(|_| ()),
+        Ok(None) => Ok(()),
+        Err(handler_error) => Err(handler_error),
+    };
+
+    if let Err(handler_error) = result {
+        Python::with_gil(|py| {
+            e.print_and_set_sys_last_vars(py);
+            handler_error.print_and_set_sys_last_vars(py);
+        });
+    }
+}
+
+/// How many finished tracks are remembered per guild for `Lavalink.previous()`.
+const PREVIOUS_TRACKS_LIMIT: usize = 10;
+
+/// The names of all the events the handler can implement.
+const EVENTS: &[&str] = &[
+    "stats",
+    "player_update",
+    "track_start",
+    "track_finish",
+    "track_exception",
+    "track_stuck",
+    "websocket_closed",
+    "player_destroyed",
+    "idle_timeout",
+    "autoplay_track",
+];
+
+/// Methods with shorter names are never reported as typos of an event.
+const MIN_TYPO_LEN: usize = 6;
+
+fn warn_event(py: Python, message: &str) {
+    let category = py.get_type::<pyo3::exceptions::PyRuntimeWarning>();
+
+    if let Err(e) = PyErr::warn(py, category, message, 1) {
+        e.print_and_set_sys_last_vars(py);
+    }
+}
+
+/// Warns about methods of the event handler that look like typos of an event, as they would
+/// never be called.
+pub fn warn_misspelled_events(py: Python, event_handler: &PyAny) -> PyResult<()> {
+    for attr in event_handler.dir() {
+        let attr = attr.extract::<&str>()?;
+
+        // Short names are too close to too many words, like `state` to `stats`.
+        if attr.starts_with('_') || attr.len() < MIN_TYPO_LEN || EVENTS.contains(&attr) {
+            continue;
+        }
+
+        let max_distance = if attr.len() > 8 { 2 } else { 1 };
+
+        if let Some(event) = EVENTS
+            .iter()
+            .find(|event| edit_distance(attr, event) <= max_distance)
+        {
+            warn_event(
+                py,
+                &format!(
+                    "`{}` looks like a typo of the `{}` event, it will never be called",
+                    attr, event
+                ),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Levenshtein distance between 2 strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
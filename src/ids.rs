@@ -0,0 +1,84 @@
+use crate::error;
+
+use pyo3::prelude::*;
+
+/// Extracts a discord ID from an `int` or a numeric `str`, as libraries return IDs as `int`, but
+/// they are often stringified by users.
+fn extract_id(ob: &PyAny, name: &str) -> PyResult<u64> {
+    if let Ok(id) = ob.extract::<u64>() {
+        return Ok(id);
+    }
+
+    ob.extract::<&str>()
+        .ok()
+        .and_then(|id| id.trim().parse::<u64>().ok())
+        .ok_or_else(|| {
+            error::ValueError::new_err(format!("{} must be an int or numeric string", name))
+        })
+}
+
+macro_rules! id_type {
+    ($name:ident, $arg:literal) => {
+        /// Discord ID argument that accepts either an `int` or a numeric `str`.
+        #[derive(Clone, Copy, Debug)]
+        pub struct $name(pub u64);
+
+        impl<'source> FromPyObject<'source> for $name {
+            fn extract(ob: &'source PyAny) -> PyResult<Self> {
+                extract_id(ob, $arg).map(Self)
+            }
+        }
+    };
+}
+
+id_type!(GuildId, "guild_id");
+id_type!(ChannelId, "channel_id");
+id_type!(UserId, "user_id");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pyo3::types::{PyFloat, PyString};
+
+    #[test]
+    fn extract_id_accepts_int_and_numeric_str() {
+        Python::with_gil(|py| {
+            let id = 81384788765712384u64;
+
+            assert_eq!(extract_id(id.into_py(py).as_ref(py), "id").unwrap(), id);
+            assert_eq!(
+                extract_id(PyString::new(py, "81384788765712384"), "id").unwrap(),
+                id
+            );
+            assert_eq!(
+                extract_id(PyString::new(py, " 81384788765712384\n"), "id").unwrap(),
+                id
+            );
+        });
+    }
+
+    #[test]
+    fn extract_id_rejects_invalid_ids() {
+        Python::with_gil(|py| {
+            let invalid: Vec<&PyAny> = vec![
+                PyString::new(py, "guild").as_ref(),
+                PyString::new(py, "-1").as_ref(),
+                PyString::new(py, "").as_ref(),
+                (-1i64).into_py(py).into_ref(py),
+                PyFloat::new(py, 1.5).as_ref(),
+                py.None().into_ref(py),
+            ];
+
+            for ob in invalid {
+                let err = extract_id(ob, "guild_id").unwrap_err();
+
+                assert!(err.is_instance::<error::ValueError>(py));
+                assert_eq!(
+                    err.value(py).to_string(),
+                    "guild_id must be an int or numeric string"
+                );
+            }
+        });
+    }
+}
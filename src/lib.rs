@@ -4,24 +4,47 @@
 extern crate log;
 
 mod builders;
+mod data;
 mod error;
 mod events;
+mod ids;
 mod model;
+mod sources;
 
 use builders::*;
+use data::*;
 use events::*;
+use ids::*;
 use model::*;
-
-use lavalink_rs::LavalinkClient;
-
+use sources::Source;
+
+use lavalink_rs::{
+    error::LavalinkError,
+    model::{
+        ConnectionInfo as LavaConnectionInfo, Node as LavaNode, Track as LavaTrack,
+        TrackQueue as LavaTrackQueue, Tracks as LavaTracks,
+    },
+    LavalinkClient,
+};
+
+use futures::FutureExt;
 use pyo3::prelude::*;
 
-use tokio::time::{sleep, Duration};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
 
+use tokio::time::{sleep, timeout, Duration, Instant};
+
+/// The `guild_id`, `channel_id` and `user_id` arguments of every method accept either an `int` or
+/// a numeric `str`, and raise `ValueError` otherwise.
 #[pyclass]
 #[derive(Clone)]
 pub struct Lavalink {
+    /// Cloned into the future of every async method, as they must be `'static`. This is cheap:
+    /// the lavalink-rs client only holds `Arc`s to its shared state, like `data` does.
     lava: LavalinkClient,
+    data: Arc<LavalinkData>,
 }
 
 #[pymethods]
@@ -66,23 +89,40 @@ impl Lavalink {
     /// await send_message(f"Joined <#{voice_channel_id}>")
     /// ```
     ///
-    /// Timing out means that there's either no permission to join the voice channel, or 5 seconds
-    /// have happened since the function was called.
+    /// Raises `NoConnectionInfo` if discord didn't send any voice information, which usually means
+    /// that there's no permission to join the voice channel, and `TimeoutError` if the timeout has
+    /// passed since the function was called.
+    ///
+    /// `timeout_secs` overrides the voice timeout configured on the builder (5 seconds by default)
+    /// for this call only, which is useful for guilds on distant voice regions.
     ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
     /// - `channel_id` : `Unsigned 64 bit integer`
     ///
-    /// Returns: `Future<Result<ConnectionInfo, builtins.TimeoutError>>`
-    #[pyo3(text_signature = "($self, guild_id, channel_id, /)")]
-    fn join<'a>(&self, py: Python<'a>, guild_id: u64, channel_id: u64) -> PyResult<&'a PyAny> {
+    /// Arguments:
+    /// - `timeout_secs` : `Optional Unsigned 64 bit integer` -- defaults to the builder voice timeout
+    ///
+    /// Returns: `Future<Result<ConnectionInfo, [builtins.TimeoutError, lavasnek_rs.NoConnectionInfo]>>`
+    #[pyo3(text_signature = "($self, guild_id, channel_id, /, timeout_secs=None)")]
+    #[args(timeout_secs = "None")]
+    fn join<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        timeout_secs: Option<u64>,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let channel_id = channel_id.0;
         let lava_client = self.lava.clone();
+        let voice_timeout = timeout_secs
+            .map(Duration::from_secs)
+            .unwrap_or(self.data.config.voice_timeout);
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            let connection_info = lava_client
-                .join(guild_id, channel_id)
-                .await
-                .map_err(|e| error::TimeoutError::new_err(e.to_string()))?;
+            let connection_info =
+                join_with_timeout(&lava_client, guild_id, channel_id, voice_timeout).await?;
 
             Ok(ConnectionInfo {
                 inner: connection_info,
@@ -102,35 +142,243 @@ impl Lavalink {
     /// await send_message("Left voice channel")
     /// ```
     ///
-    /// Timing out means that 5 seconds have happened since the function was called.
+    /// Timing out means that `timeout_secs`, or the voice timeout configured on the builder (5
+    /// seconds by default) if it's not given, has passed since the function was called.
     ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
     ///
+    /// Arguments:
+    /// - `timeout_secs` : `Optional Unsigned 64 bit integer` -- defaults to the builder voice timeout
+    ///
     /// Returns: `Future<Result<None, builtins.TimeoutError>>`
-    #[pyo3(text_signature = "($self, guild_id, /)")]
-    fn leave<'a>(&self, py: Python<'a>, guild_id: u64) -> PyResult<&'a PyAny> {
+    #[pyo3(text_signature = "($self, guild_id, /, timeout_secs=None)")]
+    #[args(timeout_secs = "None")]
+    fn leave<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        timeout_secs: Option<u64>,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+        let voice_timeout = timeout_secs
+            .map(Duration::from_secs)
+            .unwrap_or(self.data.config.voice_timeout);
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            leave_with_timeout(&lava_client, guild_id, voice_timeout).await?;
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Joins a guild's voice channel, waits for the full connection information and creates the
+    /// Lavalink session with it, all in one go.
+    ///
+    /// ```py
+    /// lavalink: lavasnek_rs.Lavalink = ...
+    ///
+    /// await lavalink.join_and_create(guild_id, voice_channel_id)
+    ///
+    /// await send_message(f"Joined <#{voice_channel_id}>")
+    /// ```
+    ///
+    /// Timing out means that there's either no permission to join the voice channel, or the voice
+    /// timeout configured on the builder has passed since the function was called.
+    ///
+    /// This can raise a TypeError if a necessary field of ConnectionInfo is missing.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `channel_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<ConnectionInfo, [builtins.TimeoutError, builtins.TypeError]>>`
+    #[pyo3(text_signature = "($self, guild_id, channel_id, /)")]
+    fn join_and_create<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let channel_id = channel_id.0;
         let lava_client = self.lava.clone();
+        let voice_timeout = self.data.config.voice_timeout;
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
+            let connection_info =
+                join_with_timeout(&lava_client, guild_id, channel_id, voice_timeout).await?;
+
             lava_client
-                .leave(guild_id)
+                .create_session(&connection_info)
                 .await
-                .map_err(|e| error::TimeoutError::new_err(e.to_string()))?;
+                .map_err(|e| error::TypeError::new_err(format!("Missing field '{}'", e)))?;
 
-            Ok(Python::with_gil(|py| py.None()))
+            Ok(ConnectionInfo {
+                inner: connection_info,
+            })
+        })
+    }
+
+    /// Moves the bot to a different voice channel of the same guild, without losing the Node and its
+    /// queue.
+    ///
+    /// The session is created again with the new connection information, so the player keeps
+    /// going on the new channel.
+    ///
+    /// ```py
+    /// lavalink: lavasnek_rs.Lavalink = ...
+    ///
+    /// await lavalink.move_to(guild_id, new_voice_channel_id)
+    ///
+    /// await send_message(f"Moved to <#{new_voice_channel_id}>")
+    /// ```
+    ///
+    /// Timing out means that there's either no permission to join the new voice channel, or the
+    /// voice timeout configured on the builder has passed since the function was called.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `channel_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<ConnectionInfo, [builtins.TimeoutError, builtins.TypeError]>>`
+    #[pyo3(text_signature = "($self, guild_id, channel_id, /)")]
+    fn move_to<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let channel_id = channel_id.0;
+        let lava_client = self.lava.clone();
+        let voice_timeout = self.data.config.voice_timeout;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let connection_info =
+                rejoin_keeping_node(&lava_client, guild_id, channel_id, voice_timeout).await?;
+
+            Ok(ConnectionInfo {
+                inner: connection_info,
+            })
+        })
+    }
+
+    /// Handles the voice websocket being closed, based on the close code discord sent, which can be
+    /// obtained from `WebSocketClosed.code`.
+    ///
+    /// - `4006` (session no longer valid), `4009` (session timed out) and `4015` (voice server
+    /// crashed) will join the same voice channel again and create a new session, keeping the Node.
+    /// - `4014` (disconnected, kicked or channel deleted) and the other errors that can't be
+    /// recovered from (`4004`, `4011`, `4012`, `4016`) will destroy the session, remove the guild
+    /// Node and loop, and leave the voice channel.
+    /// - Any other code is ignored.
+    ///
+    /// ```py
+    /// class EventHandler:
+    ///     async def websocket_closed(self, lavalink, event):
+    ///         action = await lavalink.handle_voice_disconnect(event.guild_id, event.code)
+    ///         logging.info(f"Voice websocket closed on {event.guild_id}: {action}")
+    /// ```
+    ///
+    /// If the voice channel to join again is not known, the guild is torn down instead.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `code` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<String, [builtins.TimeoutError, builtins.TypeError, lavasnek_rs.NetworkError]>>`
+    /// -- `"reconnected"`, `"disconnected"` or `"ignored"`
+    #[pyo3(text_signature = "($self, guild_id, code, /)")]
+    fn handle_voice_disconnect<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        code: u64,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+        let voice_timeout = self.data.config.voice_timeout;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let channel_id = lava_client
+                .discord_gateway_connections()
+                .get(&guild_id.into())
+                .and_then(|con| con.channel_id.as_ref().map(|channel_id| channel_id.0));
+
+            match (code, channel_id) {
+                (4006 | 4009 | 4015, Some(channel_id)) => {
+                    rejoin_keeping_node(&lava_client, guild_id, channel_id, voice_timeout).await?;
+
+                    Ok("reconnected")
+                }
+                (4004 | 4006 | 4009 | 4011 | 4012 | 4014 | 4015 | 4016, _) => {
+                    teardown_guild(&lava_client, guild_id, voice_timeout).await?;
+
+                    Ok("disconnected")
+                }
+                _ => Ok("ignored"),
+            }
+        })
+    }
+
+    /// Leaves the voice channel if there are no users left on it, destroying the session and
+    /// removing the guild Node and loop first.
+    ///
+    /// lavasnek_rs doesn't know who is in the voice channel, so pass the number of users in it that
+    /// are not bots, from the voice state cache of your discord library.
+    ///
+    /// ```py
+    /// async def on_voice_state_update(event):
+    ///     users = [s for s in voice_states_in(channel_id) if not s.member.is_bot]
+    ///
+    ///     if await lavalink.leave_if_alone(event.guild_id, len(users)):
+    ///         logging.info(f"Left {event.guild_id}, nobody was listening")
+    /// ```
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `user_count` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<bool, [builtins.TimeoutError, lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    /// -- `True` if it left
+    #[pyo3(text_signature = "($self, guild_id, user_count, /)")]
+    fn leave_if_alone<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        user_count: u64,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+        let voice_timeout = self.data.config.voice_timeout;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            if user_count != 0 {
+                return Ok(false);
+            }
+
+            teardown_guild(&lava_client, guild_id, voice_timeout).await?;
+
+            Ok(true)
         })
     }
 
     /// Creates a session in Lavalink with a voice connection. This also creates a Node and inserts it.
     /// The node is not added to the loops unless `PlayBuilder.queue()` is ran.
     ///
+    /// If the guild already has a Node, like when this is called twice, the voice connection is
+    /// updated, but the existing Node is kept, with its queue and data. Tracks queued while the
+    /// session was being created are added after its queue.
+    ///
     /// This can raise a TypeError if a necessary field of ConnectionInfo is missing.
     ///
     /// Positional Arguments:
     /// - `connection_info` : `ConnectionInfo` (obtained from `Lavalink.join()`)
     ///
-    /// Returns: `Future<Result<None, builtins.TypeError>>`
+    /// Returns: `Future<Result<bool, builtins.TypeError>>`
+    /// -- `True` if a new Node was created, `False` if the existing one was reused
     #[pyo3(text_signature = "($self, connection_info, /)")]
     fn create_session<'a>(
         &self,
@@ -140,12 +388,29 @@ impl Lavalink {
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
+            let guild_id = connection_info.inner.guild_id.map(|id| id.0);
+            let previous_node = match guild_id {
+                Some(guild_id) => lava_client
+                    .nodes()
+                    .await
+                    .get(&guild_id)
+                    .map(|node| node.clone()),
+                None => None,
+            };
+
             lava_client
                 .create_session(&connection_info.inner)
                 .await
                 .map_err(|e| error::TypeError::new_err(format!("Missing field '{}'", e)))?;
 
-            Ok(Python::with_gil(|py| py.None()))
+            // Creating the session inserts a new Node, so the previous one is merged back.
+            match (guild_id, previous_node) {
+                (Some(guild_id), Some(node)) => {
+                    restore_node(&lava_client, guild_id, node).await;
+                    Ok(false)
+                }
+                _ => Ok(true),
+            }
         })
     }
 
@@ -154,7 +419,7 @@ impl Lavalink {
     /// This method does not remove the guild from the running event loops, nor does it clear the Node,
     /// this allows for reconnecting without losing data. If you are having issues with disconnecting
     /// and reconnecting the bot to a voice channel, remove the guild from the running event loops and
-    /// reset the nodes.
+    /// reset the nodes, or use `flush_guild()` to do both.
     ///
     /// ```py
     /// lavalink.remove_guild_node(guild_id)
@@ -166,21 +431,158 @@ impl Lavalink {
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
+    /// Returns: `Future<Result<None, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
     #[pyo3(text_signature = "($self, guild_id, /)")]
-    fn destroy<'a>(&self, py: Python<'a>, guild_id: u64) -> PyResult<&'a PyAny> {
+    fn destroy<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
             lava_client
                 .destroy(guild_id)
                 .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+                .map_err(error::player_error)?;
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Removes the Node of the guild and removes the guild from the running event loops, in that
+    /// order, so the queue loop can't start the next track in between. Use this to clean up before
+    /// reconnecting to a voice channel.
+    ///
+    /// If `destroy` is `True`, the Lavalink session is destroyed first, like with `destroy()`.
+    ///
+    /// The history of `get_history()` is also cleared.
+    ///
+    /// This does not leave the voice channel.
+    ///
+    /// This can raise an exception if a network error happens while destroying the session.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Arguments:
+    /// - `destroy` : `bool` -- defaults to False
+    ///
+    /// Returns: `Future<Result<None, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    #[pyo3(text_signature = "($self, guild_id, /, destroy=False)")]
+    #[args(destroy = "false")]
+    fn flush_guild<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        destroy: bool,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+        let data = self.data.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            if destroy {
+                lava_client
+                    .destroy(guild_id)
+                    .await
+                    .map_err(error::player_error)?;
+            }
+
+            lava_client.nodes().await.remove(&guild_id);
+            lava_client.loops().await.remove(&guild_id);
+            data.history.clear(guild_id);
+            data.paused_by_all.lock().remove(&guild_id);
+            data.pending_volumes.lock().remove(&guild_id);
 
             Ok(Python::with_gil(|py| py.None()))
         })
     }
 
+    /// Destroys the Lavalink session of every guild with a Node, and removes them from the running
+    /// event loops. Useful to shut down the bot cleanly.
+    ///
+    /// If `leave` is `True`, it will also leave the voice channel of every guild with a Node or an
+    /// active discord gateway connection.
+    ///
+    /// The guilds are torn down at the same time, and leaving each voice channel waits up to the
+    /// voice timeout configured on the builder. Errors will not stop the teardown of the remaining
+    /// guilds, instead they are collected and returned as a dictionary of guild ID to error
+    /// message.
+    ///
+    /// ```py
+    /// lavalink: lavasnek_rs.Lavalink = ...
+    ///
+    /// failed = await lavalink.destroy_all(leave=True)
+    ///
+    /// for guild_id, error in failed.items():
+    ///     logging.warning(f"Failed to clean up {guild_id}: {error}")
+    /// ```
+    ///
+    /// Arguments:
+    /// - `leave` : `bool` -- defaults to False
+    ///
+    /// Returns: `Future<Dict<Unsigned 64 bit integer, String>>`
+    #[pyo3(text_signature = "($self, /, leave=False)")]
+    #[args(leave = "false")]
+    fn destroy_all<'a>(&self, py: Python<'a>, leave: bool) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+        let voice_timeout = self.data.config.voice_timeout;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let node_guilds = lava_client
+                .nodes()
+                .await
+                .iter()
+                .map(|node| *node.key())
+                .collect::<HashSet<_>>();
+
+            let mut guilds = node_guilds.clone();
+
+            if leave {
+                guilds.extend(
+                    lava_client
+                        .discord_gateway_connections()
+                        .iter()
+                        .map(|con| con.key().0),
+                );
+            }
+
+            // Every guild is torn down at the same time, so a slow one doesn't hold up the rest.
+            let teardowns = guilds.into_iter().map(|guild_id| {
+                let lava_client = lava_client.clone();
+                let has_node = node_guilds.contains(&guild_id);
+
+                async move {
+                    let mut error = None;
+
+                    if has_node {
+                        if let Err(e) = lava_client.destroy(guild_id).await {
+                            error = Some(e.to_string());
+                        }
+
+                        lava_client.loops().await.remove(&guild_id);
+                    }
+
+                    if leave {
+                        if let Err(e) =
+                            leave_with_timeout(&lava_client, guild_id, voice_timeout).await
+                        {
+                            error.get_or_insert_with(|| e.to_string());
+                        }
+                    }
+
+                    error.map(|error| (guild_id, error))
+                }
+            });
+
+            let failed = futures::future::join_all(teardowns)
+                .await
+                .into_iter()
+                .flatten()
+                .collect::<HashMap<_, _>>();
+
+            Ok(failed)
+        })
+    }
+
     /// Returns the Play builder.
     ///
     /// ```py
@@ -207,123 +609,729 @@ impl Lavalink {
     ///
     /// Returns: `PlayBuilder`
     #[pyo3(text_signature = "($self, guild_id, track, /)")]
-    fn play(&self, guild_id: u64, track: Track) -> PlayBuilder {
+    fn play(&self, guild_id: GuildId, track: Track) -> PlayBuilder {
+        let guild_id = guild_id.0;
+
         PlayBuilder {
             builder: self.lava.play(guild_id, track.inner),
+            lava: self.lava.clone(),
+            guild_id,
+            volume: None,
+            data: self.data.clone(),
         }
     }
 
-    /// Returns the tracks from the URL or query provided.
+    /// Queues only a part of a track, from `start_ms` to `end_ms`, like for soundboard clips.
     ///
-    /// This can raise an exception if a network error happens.
+    /// This is a shortcut for:
     ///
-    /// Positional Arguments:
-    /// - `query` : `String`
+    /// ```py
+    /// await (
+    ///     lavalink.play(guild_id, track)
+    ///     .start_time_millis(start_ms)
+    ///     .finish_time_millis(end_ms)
+    ///     .requester(requester)
+    ///     .queue()
+    /// )
+    /// ```
     ///
-    /// Returns: `Future<Result<Tracks, lavasnek_rs.NetworkError>>`
-    #[pyo3(text_signature = "($self, query, /)")]
-    fn get_tracks<'a>(&self, py: Python<'a>, query: String) -> PyResult<&'a PyAny> {
-        let lava_client = self.lava.clone();
+    /// Needs for `Lavalink.create_session() to be called first.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `track` : `Track`
+    /// - `start_ms` : `Unsigned 64 bit integer`
+    /// - `end_ms` : `Unsigned 64 bit integer`
+    ///
+    /// Arguments:
+    /// - `requester` : `Optional Unsigned 64 bit integer` -- defaults to None
+    ///
+    /// Returns: `Future<Result<Unsigned 64 bit integer, [builtins.ValueError, lavasnek_rs.NoSessionPresent, lavasnek_rs.NetworkError, lavasnek_rs.QueueError]>>`
+    /// -- the same as `PlayBuilder.queue()`
+    #[pyo3(text_signature = "($self, guild_id, track, start_ms, end_ms, /, requester=None)")]
+    #[args(requester = "None")]
+    fn play_segment<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        track: Track,
+        start_ms: u64,
+        end_ms: u64,
+        requester: Option<UserId>,
+    ) -> PyResult<&'a PyAny> {
+        if end_ms <= start_ms {
+            return Err(error::ValueError::new_err(format!(
+                "end_ms ({}) must be after start_ms ({})",
+                end_ms, start_ms
+            )));
+        }
 
-        pyo3_asyncio::tokio::future_into_py(py, async move {
-            let tracks = lava_client
-                .get_tracks(query)
-                .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+        let mut builder = self.play(guild_id, track);
+        builder.builder.start = start_ms;
+        builder.builder.finish = end_ms;
+        builder.builder.requester = requester.map(|id| id.0.into());
 
-            Ok(Python::with_gil(|py| Tracks { inner: tracks }.into_py(py)))
-        })
+        builder.queue(py)
     }
 
-    /// Will automatically search the query on youtube if it's not a valid URL.
+    /// Queues the track, and returns if it started playing right away because nothing was playing,
+    /// or if it was added behind other tracks.
     ///
-    /// This can raise an exception if a network error happens.
+    /// The result is the position the track was added at, taken while the Node is locked, so a
+    /// track queued by something else at the same time is taken into account. The current track is
+    /// the first one of the queue, so being added first means nothing was playing before.
+    ///
+    /// If the queue loop was disabled with `LavalinkBuilder.set_manage_queue()`, the track is only
+    /// added to the Node, so this always returns `"queued"`.
+    ///
+    /// ```py
+    /// if await lavalink.play_or_queue(guild_id, track, ctx.author.id) == "playing":
+    ///     await ctx.respond(f"Now playing: {track.info.title}")
+    /// else:
+    ///     await ctx.respond(f"Added to the queue: {track.info.title}")
+    /// ```
+    ///
+    /// Needs for `Lavalink.create_session() to be called first.
     ///
     /// Positional Arguments:
-    /// - `query` : `String`
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `track` : `Track`
     ///
-    /// Returns: `Future<Result<Tracks, lavasnek_rs.NetworkError>>`
-    #[pyo3(text_signature = "($self, query, /)")]
-    fn auto_search_tracks<'a>(&self, py: Python<'a>, query: String) -> PyResult<&'a PyAny> {
+    /// Arguments:
+    /// - `requester` : `Optional Unsigned 64 bit integer` -- defaults to None
+    ///
+    /// Returns: `Future<Result<String, [lavasnek_rs.NoSessionPresent, lavasnek_rs.NetworkError, lavasnek_rs.QueueError]>>`
+    /// -- `"playing"` or `"queued"`
+    #[pyo3(text_signature = "($self, guild_id, track, /, requester=None)")]
+    #[args(requester = "None")]
+    fn play_or_queue<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        track: Track,
+        requester: Option<UserId>,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
+        let track_queue = LavaTrackQueue {
+            track: track.inner.clone(),
+            start_time: 0,
+            end_time: None,
+            requester: requester.map(|id| id.0.into()),
+        };
+
+        if !self.data.config.manage_queue {
+            return pyo3_asyncio::tokio::future_into_py(py, async move {
+                add_to_node(&lava_client, guild_id, track_queue).await?;
+                Ok("queued")
+            });
+        }
+
+        let mut builder = self.lava.play(guild_id, track.inner);
+        builder.requester = track_queue.requester;
+
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            let tracks = lava_client
-                .auto_search_tracks(query)
-                .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+            let position = queue_with_loop(&lava_client, guild_id, builder, track_queue).await?;
 
-            Ok(Python::with_gil(|py| Tracks { inner: tracks }.into_py(py)))
+            Ok(if position == 0 { "playing" } else { "queued" })
         })
     }
 
-    /// Returns tracks from the search query.
-    /// Uses youtube to search.
+    /// Queues a list of base64 track strings, like the ones stored from `Track.track`, without
+    /// decoding them first.
     ///
-    /// This can raise an exception if a network error happens.
+    /// The tracks are queued in order with their `info` set to `None`; use `Lavalink.decode_track()`
+    /// if the information is needed later on.
+    ///
+    /// Needs for `Lavalink.create_session() to be called first.
     ///
     /// Positional Arguments:
-    /// - `query` : `String`
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `tracks` : `List<String>`
     ///
-    /// Returns: `Future<Result<Tracks, lavasnek_rs.NetworkError>>`
-    #[pyo3(text_signature = "($self, query, /)")]
-    fn search_tracks<'a>(&self, py: Python<'a>, query: String) -> PyResult<&'a PyAny> {
+    /// Arguments:
+    /// - `requester` : `Optional Unsigned 64 bit integer` -- defaults to None
+    ///
+    /// Returns: `Future<Result<None, [lavasnek_rs.NoSessionPresent, lavasnek_rs.NetworkError, lavasnek_rs.QueueError]>>`
+    #[pyo3(text_signature = "($self, guild_id, tracks, /, requester=None)")]
+    #[args(requester = "None")]
+    fn queue_raw<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        tracks: Vec<String>,
+        requester: Option<UserId>,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
+        let manage_queue = self.data.config.manage_queue;
+
+        let mut track_queues = tracks.into_iter().map(move |track| LavaTrackQueue {
+            track: LavaTrack { track, info: None },
+            start_time: 0,
+            end_time: None,
+            requester: requester.map(|id| id.0.into()),
+        });
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            let tracks = lava_client
-                .search_tracks(query)
-                .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+            // Queueing the first track through lavalink-rs starts the queue loop if it's not
+            // running, the rest can be added to the Node directly.
+            if manage_queue {
+                if let Some(first) = track_queues.next() {
+                    let mut builder = lava_client.play(guild_id, first.track);
+                    builder.requester = first.requester;
+                    builder.queue().await.map_err(error::queue_error)?;
+                }
+            }
 
-            Ok(Python::with_gil(|py| Tracks { inner: tracks }.into_py(py)))
+            {
+                let nodes = lava_client.nodes().await;
+                let mut node = nodes.get_mut(&guild_id).ok_or_else(|| {
+                    error::NoSessionPresent::new_err(LavalinkError::NoSessionPresent.to_string())
+                })?;
+
+                node.queue.extend(track_queues);
+            }
+
+            Ok(Python::with_gil(|py| py.None()))
         })
     }
 
-    /// Returns information from a track.
+    /// Returns the tracks from the URL or query provided.
+    ///
+    /// Spotify, Apple Music and Deezer URLs need the LavaSrc plugin on the Lavalink server. If
+    /// they load nothing and the server doesn't have the source, `LoadFailedError` is raised
+    /// instead of returning empty `Tracks`.
     ///
     /// This can raise an exception if a network error happens.
     ///
+    /// If `raise_on_failure` is `True`, `LoadFailedError` will be raised when Lavalink fails to
+    /// load the query, instead of returning `Tracks` with a `load_type` of `LOAD_FAILED`.
+    ///
     /// Positional Arguments:
-    /// - `track` : `String` -- base 64
+    /// - `query` : `String`
     ///
-    /// Returns: `Future<Result<Info, lavasnek_rs.NetworkError>>`
-    #[pyo3(text_signature = "($self, track, /)")]
-    fn decode_track<'a>(&self, py: Python<'a>, track: String) -> PyResult<&'a PyAny> {
+    /// Arguments:
+    /// - `raise_on_failure` : `bool` -- defaults to False
+    ///
+    /// Returns: `Future<Result<Tracks, [lavasnek_rs.NetworkError, builtins.TimeoutError, lavasnek_rs.LoadFailedError]>>`
+    #[pyo3(text_signature = "($self, query, /, raise_on_failure=False)")]
+    #[args(raise_on_failure = "false")]
+    fn get_tracks<'a>(
+        &self,
+        py: Python<'a>,
+        query: String,
+        raise_on_failure: bool,
+    ) -> PyResult<&'a PyAny> {
         let lava_client = self.lava.clone();
+        let rest = self.data.rest.clone();
+        let rest_timeout = self.data.config.rest_timeout;
+        let rest_retries = self.data.config.rest_retries;
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            let track_decode = lava_client
-                .decode_track(track)
-                .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+            let tracks = rest_with_retries(rest_timeout, rest_retries, || {
+                lava_client.get_tracks(&query)
+            })
+            .await?;
 
-            Ok(Python::with_gil(|py| {
-                Info {
-                    inner: track_decode,
+            if tracks.tracks.is_empty() {
+                if let Some(source) = sources::plugin_source_for_url(&query) {
+                    ensure_plugin_source(&rest, rest_timeout, source).await?;
                 }
-                .into_py(py)
-            }))
+            }
+
+            if raise_on_failure && tracks.load_type == "LOAD_FAILED" {
+                return Err(load_failed_error(&rest, rest_timeout, &query).await);
+            }
+
+            Ok(Python::with_gil(|py| Tracks { inner: tracks }.into_py(py)))
         })
     }
 
-    /// Stops the current player.
+    /// Will automatically search the query on youtube if it's not a valid URL.
     ///
-    /// This can raise an exception if a network error happens.
+    /// If search sources were set with `LavalinkBuilder.set_search_sources()`, they are tried in
+    /// order instead, and the first non-empty `Tracks` is returned. URLs are always loaded
+    /// directly, without searching.
     ///
-    /// Positional Arguments:
+    /// Search results are cached if enabled with `LavalinkBuilder.set_search_cache()`, URLs are
+    /// not.
+    ///
+    /// Spotify, Apple Music and Deezer URLs need the LavaSrc plugin on the Lavalink server. If
+    /// they load nothing and the server doesn't have the source, `LoadFailedError` is raised
+    /// instead of returning empty `Tracks`.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// If `raise_on_failure` is `True`, `LoadFailedError` will be raised when Lavalink fails to
+    /// load the query, instead of returning `Tracks` with a `load_type` of `LOAD_FAILED`.
+    ///
+    /// Positional Arguments:
+    /// - `query` : `String`
+    ///
+    /// Arguments:
+    /// - `raise_on_failure` : `bool` -- defaults to False
+    ///
+    /// Returns: `Future<Result<Tracks, [lavasnek_rs.NetworkError, builtins.TimeoutError, lavasnek_rs.LoadFailedError]>>`
+    #[pyo3(text_signature = "($self, query, /, raise_on_failure=False)")]
+    #[args(raise_on_failure = "false")]
+    fn auto_search_tracks<'a>(
+        &self,
+        py: Python<'a>,
+        query: String,
+        raise_on_failure: bool,
+    ) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+        let data = self.data.clone();
+        let rest = self.data.rest.clone();
+        let rest_timeout = self.data.config.rest_timeout;
+        let rest_retries = self.data.config.rest_retries;
+        let search_sources = self.data.config.search_sources.clone();
+
+        let cache = match self.data.config.search_cache {
+            Some(config) if !sources::is_url(&query) => {
+                let prefix = if search_sources.is_empty() {
+                    "ytsearch".to_string()
+                } else {
+                    search_sources
+                        .iter()
+                        .map(|source| source.prefix)
+                        .collect::<Vec<_>>()
+                        .join(",")
+                };
+
+                Some((config, SearchCache::key(&prefix, &query)))
+            }
+            _ => None,
+        };
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            if let Some((config, key)) = &cache {
+                if let Some(tracks) = data.search_cache.get(config, key) {
+                    return Ok(Python::with_gil(|py| Tracks { inner: tracks }.into_py(py)));
+                }
+            }
+
+            let (tracks, identifier) = if search_sources.is_empty() || sources::is_url(&query) {
+                let tracks = rest_with_retries(rest_timeout, rest_retries, || {
+                    lava_client.auto_search_tracks(&query)
+                })
+                .await?;
+
+                if tracks.tracks.is_empty() {
+                    if let Some(source) = sources::plugin_source_for_url(&query) {
+                        ensure_plugin_source(&rest, rest_timeout, source).await?;
+                    }
+                }
+
+                let identifier = if sources::is_url(&query) {
+                    query.clone()
+                } else {
+                    format!("ytsearch:{}", query)
+                };
+
+                (tracks, identifier)
+            } else {
+                let mut tracks = None;
+
+                for source in &search_sources {
+                    let search = format!("{}:{}", source.prefix, query);
+                    let found = rest_with_retries(rest_timeout, rest_retries, || {
+                        lava_client.get_tracks(&search)
+                    })
+                    .await?;
+
+                    let is_empty = found.tracks.is_empty();
+                    tracks = Some((found, search));
+
+                    if !is_empty {
+                        break;
+                    }
+                }
+
+                // `search_sources` is not empty, so at least one search was done.
+                let (tracks, identifier) = tracks.unwrap();
+
+                // A missing plugin only matters if no other source found anything.
+                if tracks.tracks.is_empty() {
+                    for source in &search_sources {
+                        ensure_plugin_source(&rest, rest_timeout, source).await?;
+                    }
+                }
+
+                (tracks, identifier)
+            };
+
+            if let Some((config, key)) = cache {
+                if !tracks.tracks.is_empty() {
+                    data.search_cache.insert(&config, key, tracks.clone());
+                }
+            }
+
+            if raise_on_failure && tracks.load_type == "LOAD_FAILED" {
+                return Err(load_failed_error(&rest, rest_timeout, &identifier).await);
+            }
+
+            Ok(Python::with_gil(|py| Tracks { inner: tracks }.into_py(py)))
+        })
+    }
+
+    /// Returns tracks from the search query.
+    /// Uses youtube to search.
+    ///
+    /// The result is cached if enabled with `LavalinkBuilder.set_search_cache()`.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `query` : `String`
+    ///
+    /// Returns: `Future<Result<Tracks, [lavasnek_rs.NetworkError, builtins.TimeoutError]>>`
+    #[pyo3(text_signature = "($self, query, /)")]
+    fn search_tracks<'a>(&self, py: Python<'a>, query: String) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+        let data = self.data.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let tracks = search_youtube(&lava_client, &data, query).await?;
+
+            Ok(Python::with_gil(|py| Tracks { inner: tracks }.into_py(py)))
+        })
+    }
+
+    /// Returns tracks from the search query, searching on the source provided.
+    ///
+    /// The available sources are:
+    /// - `"youtube"` : `ytsearch:`
+    /// - `"youtube_music"` : `ytmsearch:`
+    /// - `"soundcloud"` : `scsearch:`
+    /// - `"spotify"` : `spsearch:` -- needs the LavaSrc plugin.
+    /// - `"apple_music"` : `amsearch:` -- needs the LavaSrc plugin.
+    /// - `"deezer"` : `dzsearch:` -- needs the LavaSrc plugin.
+    ///
+    /// The sources from the LavaSrc plugin are checked with `node_info()` first, and
+    /// `LoadFailedError` is raised if the Lavalink server doesn't have them.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `query` : `String`
+    ///
+    /// Arguments:
+    /// - `source` : `String` -- defaults to "youtube"
+    ///
+    /// Returns: `Future<Result<Tracks, [builtins.ValueError, lavasnek_rs.NetworkError, builtins.TimeoutError, lavasnek_rs.LoadFailedError]>>`
+    #[pyo3(text_signature = "($self, query, /, source=\"youtube\")")]
+    #[args(source = "\"youtube\"")]
+    fn search_tracks_with_source<'a>(
+        &self,
+        py: Python<'a>,
+        query: String,
+        source: &str,
+    ) -> PyResult<&'a PyAny> {
+        let source = sources::source_by_name(source)?;
+
+        let lava_client = self.lava.clone();
+        let rest = self.data.rest.clone();
+        let rest_timeout = self.data.config.rest_timeout;
+        let rest_retries = self.data.config.rest_retries;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            ensure_plugin_source(&rest, rest_timeout, source).await?;
+
+            let query = format!("{}:{}", source.prefix, query);
+            let tracks = rest_with_retries(rest_timeout, rest_retries, || {
+                lava_client.get_tracks(&query)
+            })
+            .await?;
+
+            Ok(Python::with_gil(|py| Tracks { inner: tracks }.into_py(py)))
+        })
+    }
+
+    /// Returns the first track from the search query, or `None` if nothing was found.
+    /// Uses youtube to search.
+    ///
+    /// This is a shortcut for `search_tracks()` that skips building the other tracks, and it shares
+    /// its cache, if enabled with `LavalinkBuilder.set_search_cache()`.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `query` : `String`
+    ///
+    /// Returns: `Future<Result<Option<Track>, [lavasnek_rs.NetworkError, builtins.TimeoutError]>>`
+    #[pyo3(text_signature = "($self, query, /)")]
+    fn search_first<'a>(&self, py: Python<'a>, query: String) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+        let data = self.data.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let tracks = search_youtube(&lava_client, &data, query).await?;
+
+            Ok(tracks
+                .tracks
+                .into_iter()
+                .next()
+                .map(|track| Track { inner: track }))
+        })
+    }
+
+    /// Returns the title and URI of the tracks from the search query, without building the `Track`
+    /// objects, which makes it fast enough for slash command autocomplete.
+    /// Uses youtube to search.
+    ///
+    /// `limit` is capped to 25, as that's the maximum amount of autocomplete choices discord allows.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `query` : `String`
+    ///
+    /// Arguments:
+    /// - `limit` : `Unsigned 64 bit integer` -- defaults to 5
+    ///
+    /// Returns: `Future<Result<List<Tuple<String, String>>, [lavasnek_rs.NetworkError, builtins.TimeoutError]>>`
+    #[pyo3(text_signature = "($self, query, /, limit=5)")]
+    #[args(limit = "5")]
+    fn search_suggestions<'a>(
+        &self,
+        py: Python<'a>,
+        query: String,
+        limit: usize,
+    ) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+        let rest_timeout = self.data.config.rest_timeout;
+        let rest_retries = self.data.config.rest_retries;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let tracks = rest_with_retries(rest_timeout, rest_retries, || {
+                lava_client.search_tracks(query.clone())
+            })
+            .await?;
+
+            let suggestions = tracks
+                .tracks
+                .into_iter()
+                .filter_map(|track| track.info)
+                .map(|info| (info.title, info.uri))
+                .take(limit.min(25))
+                .collect::<Vec<_>>();
+
+            Ok(suggestions)
+        })
+    }
+
+    /// Returns information from a track.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `track` : `String` -- base 64
+    ///
+    /// Returns: `Future<Result<Info, [lavasnek_rs.NetworkError, builtins.TimeoutError]>>`
+    #[pyo3(text_signature = "($self, track, /)")]
+    fn decode_track<'a>(&self, py: Python<'a>, track: String) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+        let rest_timeout = self.data.config.rest_timeout;
+        let rest_retries = self.data.config.rest_retries;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let track_decode = rest_with_retries(rest_timeout, rest_retries, || {
+                lava_client.decode_track(track.clone())
+            })
+            .await?;
+
+            Ok(Python::with_gil(|py| {
+                Info {
+                    inner: track_decode,
+                }
+                .into_py(py)
+            }))
+        })
+    }
+
+    /// Returns the information of the Lavalink server, like the version, build time, source
+    /// managers and loaded plugins, as a dict.
+    ///
+    /// This uses the `/v3/info` endpoint. Servers older than 3.7 don't have it, so only the
+    /// version is returned for them, as `{"version": {"semver": "..."}}`.
+    ///
+    /// ```py
+    /// info = await lavalink.node_info()
+    /// plugins = [plugin["name"] for plugin in info.get("plugins", [])]
+    /// ```
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Returns: `Future<Result<Dict<String, Any>, [lavasnek_rs.NetworkError, builtins.TimeoutError]>>`
+    #[pyo3(text_signature = "($self, /)")]
+    fn node_info<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let rest = self.data.rest.clone();
+        let rest_timeout = self.data.config.rest_timeout;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let response = rest_with_timeout(rest_timeout, rest.get("/v3/info").send()).await?;
+
+            let info = if response.status() == reqwest::StatusCode::NOT_FOUND {
+                let version = rest_with_timeout(rest_timeout, async {
+                    rest.get("/version")
+                        .send()
+                        .await?
+                        .error_for_status()?
+                        .text()
+                        .await
+                })
+                .await?;
+
+                serde_json::json!({ "version": { "semver": version } })
+            } else {
+                rest_with_timeout(rest_timeout, async {
+                    response
+                        .error_for_status()?
+                        .json::<serde_json::Value>()
+                        .await
+                })
+                .await?
+            };
+
+            Python::with_gil(|py| Ok(pythonize::pythonize(py, &info)?))
+        })
+    }
+
+    /// Checks if the Lavalink server is reachable, and returns the round trip time of the request in
+    /// milliseconds.
+    ///
+    /// This requests the `/version` endpoint, so it has no side effects on the players.
+    ///
+    /// This can raise an exception if the server can't be reached.
+    ///
+    /// Returns: `Future<Result<float, [lavasnek_rs.NetworkError, builtins.TimeoutError]>>`
+    #[pyo3(text_signature = "($self, /)")]
+    fn ping<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let rest = self.data.rest.clone();
+        let rest_timeout = self.data.config.rest_timeout;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let start = Instant::now();
+
+            rest_with_timeout(rest_timeout, async {
+                rest.get("/version").send().await?.error_for_status()
+            })
+            .await?;
+
+            Ok(start.elapsed().as_secs_f64() * 1000.0)
+        })
+    }
+
+    /// Returns a snapshot of every player, to show them in a dashboard.
+    ///
+    /// Each player is a dict with the keys:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `channel_id` : `Optional Unsigned 64 bit integer` -- the voice channel
+    /// - `title` : `Optional String` -- the title of the current track, if it has its info
+    /// - `position` : `Optional Signed 64 bit integer` -- the last position sent by the
+    ///   `player_update` event, in milliseconds
+    /// - `volume` : `Unsigned 64 bit integer`
+    /// - `is_paused` : `bool`
+    /// - `queue_len` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<List<Dict<String, Any>>>`
+    #[pyo3(text_signature = "($self, /)")]
+    fn get_players<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+        let data = self.data.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let nodes = lava_client.nodes().await;
+            let connections = lava_client.discord_gateway_connections();
+            let positions = data.positions.lock().clone();
+
+            let players = nodes
+                .iter()
+                .map(|node| {
+                    let guild_id = *node.key();
+
+                    serde_json::json!({
+                        "guild_id": guild_id,
+                        "channel_id": connections
+                            .get(&guild_id.into())
+                            .and_then(|con| con.channel_id.as_ref().map(|channel_id| channel_id.0)),
+                        "title": node
+                            .now_playing
+                            .as_ref()
+                            .and_then(|track_queue| track_queue.track.info.as_ref())
+                            .map(|info| info.title.clone()),
+                        "position": positions.get(&guild_id),
+                        "volume": node.volume,
+                        "is_paused": node.is_paused,
+                        "queue_len": node.queue.len(),
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            Python::with_gil(|py| Ok(pythonize::pythonize(py, &players)?))
+        })
+    }
+
+    /// Returns the last statistics sent by the Lavalink server, the same as the `stats` event.
+    ///
+    /// Returns `None` if the server didn't send them yet.
+    ///
+    /// Returns: `Option<Stats>`
+    #[pyo3(text_signature = "($self, /)")]
+    fn get_stats(&self) -> Option<Stats> {
+        self.data
+            .stats
+            .lock()
+            .clone()
+            .map(|stats| Stats { inner: stats })
+    }
+
+    /// Sets the event loop the events are run on.
+    ///
+    /// The events run on the loop that was running when `LavalinkBuilder.build()` was called, so
+    /// if the bot replaces its event loop, like when reconnecting or between tests, this has to be
+    /// called with the new one, or the events will be dropped with an error log.
+    ///
+    /// If `event_loop` is not provided, the running event loop is used.
+    ///
+    /// Arguments:
+    /// - `event_loop` : `Optional<asyncio.AbstractEventLoop>`
+    ///
+    /// Returns: `Result<(), [builtins.RuntimeError]>`
+    #[pyo3(text_signature = "($self, /, event_loop=None)")]
+    #[args(event_loop = "None")]
+    fn set_event_loop(&self, py: Python, event_loop: Option<PyObject>) -> PyResult<()> {
+        let event_loop = match event_loop {
+            Some(event_loop) => event_loop,
+            None => PyObject::from(pyo3_asyncio::get_running_loop(py)?),
+        };
+
+        *self.data.event_loop.lock() = Some(event_loop);
+
+        Ok(())
+    }
+
+    /// Stops the current player.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
+    /// Returns: `Future<Result<None, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
     #[pyo3(text_signature = "($self, guild_id, /)")]
-    fn stop<'a>(&self, py: Python<'a>, guild_id: u64) -> PyResult<&'a PyAny> {
+    fn stop<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
             lava_client
                 .stop(guild_id)
                 .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+                .map_err(error::player_error)?;
 
             Ok(Python::with_gil(|py| py.None()))
         })
@@ -356,7 +1364,8 @@ impl Lavalink {
     ///
     /// Returns: `Future<Option<TrackQueue>>`
     #[pyo3(text_signature = "($self, guild_id, /)")]
-    fn skip<'a>(&self, py: Python<'a>, guild_id: u64) -> PyResult<&'a PyAny> {
+    fn skip<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
@@ -373,6 +1382,151 @@ impl Lavalink {
         })
     }
 
+    /// Skips `count` tracks, counting the currently playing one, with a single skip.
+    ///
+    /// The next `count - 1` tracks are removed from the queue before skipping, so only 1 track
+    /// start event is sent. `count` is clamped to the length of the queue plus the current track,
+    /// and a `count` of 0 does nothing.
+    ///
+    /// Like `skip()`, if nothing is left in the queue, the currently playing track will keep
+    /// playing.
+    ///
+    /// Returns the track that plays next, which is the first one of the queue after skipping, or
+    /// `None` if the queue is empty.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `count` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Option<TrackQueue>>`
+    #[pyo3(text_signature = "($self, guild_id, count, /)")]
+    fn skip_many<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        count: usize,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            if count == 0 {
+                return Ok(Python::with_gil(|py| py.None()));
+            }
+
+            if let Some(mut node) = lava_client.nodes().await.get_mut(&guild_id) {
+                // While playing, `queue[0]` is the current track, which `skip()` removes.
+                let start = if node.now_playing.is_some() {
+                    node.queue.len().min(1)
+                } else {
+                    0
+                };
+                let end = (start + count - 1).min(node.queue.len());
+                node.queue.drain(start..end);
+            }
+
+            lava_client.skip(guild_id).await;
+
+            let track = lava_client
+                .nodes()
+                .await
+                .get(&guild_id)
+                .and_then(|node| node.queue.first().cloned());
+
+            Ok(Python::with_gil(|py| {
+                if let Some(track) = track {
+                    TrackQueue { inner: track }.into_py(py)
+                } else {
+                    py.None()
+                }
+            }))
+        })
+    }
+
+    /// Plays the track right away, keeping the rest of the queue, and returns the track that was
+    /// playing before.
+    ///
+    /// The track is put right after the current track, which is `queue[0]`, and the current track
+    /// is skipped, so the queue loop starts it like any other track. If `requeue` is `True`, the
+    /// previous track is put back after it, and it will start over from its start time once the
+    /// new track finishes.
+    ///
+    /// If nothing is playing, the track is just queued, and `None` is returned.
+    ///
+    /// Needs for `Lavalink.create_session() to be called first.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `track` : `Track`
+    ///
+    /// Arguments:
+    /// - `requeue` : `bool` -- defaults to True
+    ///
+    /// Returns: `Future<Result<Option<TrackQueue>, [lavasnek_rs.NoSessionPresent, lavasnek_rs.NetworkError, lavasnek_rs.QueueError]>>`
+    #[pyo3(text_signature = "($self, guild_id, track, /, requeue=True)")]
+    #[args(requeue = "true")]
+    fn replace_now_playing<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        track: Track,
+        requeue: bool,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let previous = {
+                let nodes = lava_client.nodes().await;
+                let mut node = nodes.get_mut(&guild_id).ok_or_else(|| {
+                    error::NoSessionPresent::new_err(LavalinkError::NoSessionPresent.to_string())
+                })?;
+
+                let previous = node.now_playing.clone();
+
+                if let Some(previous) = &previous {
+                    // `queue[0]` is the current track, which `skip()` removes.
+                    let index = node.queue.len().min(1);
+
+                    node.queue.insert(
+                        index,
+                        LavaTrackQueue {
+                            track: track.inner.clone(),
+                            start_time: 0,
+                            end_time: None,
+                            requester: None,
+                        },
+                    );
+
+                    if requeue {
+                        node.queue.insert(index + 1, previous.clone());
+                    }
+                }
+
+                previous
+            };
+
+            match previous {
+                Some(previous) => {
+                    lava_client.skip(guild_id).await;
+
+                    Ok(Python::with_gil(|py| {
+                        TrackQueue { inner: previous }.into_py(py)
+                    }))
+                }
+                None => {
+                    lava_client
+                        .play(guild_id, track.inner)
+                        .queue()
+                        .await
+                        .map_err(error::queue_error)?;
+
+                    Ok(Python::with_gil(|py| py.None()))
+                }
+            }
+        })
+    }
+
     /// Sets the pause status.
     ///
     /// This can raise an exception if a network error happens.
@@ -381,16 +1535,17 @@ impl Lavalink {
     /// - `guild_id` : `Unsigned 64 bit integer`
     /// - `pause` : `bool`
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
+    /// Returns: `Future<Result<None, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
     #[pyo3(text_signature = "($self, guild_id, pause, /)")]
-    fn set_pause<'a>(&self, py: Python<'a>, guild_id: u64, pause: bool) -> PyResult<&'a PyAny> {
+    fn set_pause<'a>(&self, py: Python<'a>, guild_id: GuildId, pause: bool) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
             lava_client
                 .set_pause(guild_id, pause)
                 .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+                .map_err(error::player_error)?;
 
             Ok(Python::with_gil(|py| py.None()))
         })
@@ -398,45 +1553,186 @@ impl Lavalink {
 
     /// Sets pause status to `True`
     #[pyo3(text_signature = "($self, guild_id, /)")]
-    fn pause<'a>(&self, py: Python<'a>, guild_id: u64) -> PyResult<&'a PyAny> {
+    fn pause<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
         self.set_pause(py, guild_id, true)
     }
 
     /// Sets pause status to `False`
     #[pyo3(text_signature = "($self, guild_id, /)")]
-    fn resume<'a>(&self, py: Python<'a>, guild_id: u64) -> PyResult<&'a PyAny> {
+    fn resume<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
         self.set_pause(py, guild_id, false)
     }
 
-    /// Jumps to a specific time in the currently playing track.
+    /// Flips the pause status of the player, and returns the new status.
+    ///
+    /// Returns `None` without doing anything if the guild doesn't have a Node.
     ///
     /// This can raise an exception if a network error happens.
     ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
-    /// - `time` : `Unsigned 64 bit integer`
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
-    #[pyo3(text_signature = "($self, guild_id, time, /)")]
-    fn seek_secs<'a>(&self, py: Python<'a>, guild_id: u64, time: u64) -> PyResult<&'a PyAny> {
+    /// Returns: `Future<Result<Option<bool>, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn pause_toggle<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
+            let is_paused = match lava_client.nodes().await.get(&guild_id) {
+                Some(node) => node.is_paused,
+                None => return Ok(None),
+            };
+
             lava_client
-                .seek(guild_id, Duration::from_secs(time))
+                .set_pause(guild_id, !is_paused)
                 .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+                .map_err(error::player_error)?;
 
-            Ok(Python::with_gil(|py| py.None()))
+            if let Some(mut node) = lava_client.nodes().await.get_mut(&guild_id) {
+                node.is_paused = !is_paused;
+            }
+
+            Ok(Some(!is_paused))
         })
     }
 
-    /// Alias to `seek_secs()`
+    /// Pauses or resumes every player at the same time.
+    ///
+    /// Pausing only affects the players that are playing a track and not paused already, and they
+    /// are remembered, so resuming only affects those players, leaving alone the ones that were
+    /// paused manually before.
+    ///
+    /// ```py
+    /// paused, failed = await lavalink.set_pause_all(True)
+    /// await announce_maintenance(paused)
+    ///
+    /// ...
+    ///
+    /// await lavalink.set_pause_all(False)
+    /// ```
+    ///
+    /// Returns a list of the guilds that were paused or resumed, and a dict of the guilds that
+    /// failed, with the error message as the value.
+    ///
+    /// Positional Arguments:
+    /// - `pause` : `bool`
+    ///
+    /// Returns: `Future<Tuple<List<Unsigned 64 bit integer>, Dict<Unsigned 64 bit integer, String>>>`
+    #[pyo3(text_signature = "($self, pause, /)")]
+    fn set_pause_all<'a>(&self, py: Python<'a>, pause: bool) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+        let data = self.data.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let target_guilds = {
+                let nodes = lava_client.nodes().await;
+
+                if pause {
+                    nodes
+                        .iter()
+                        .filter(|node| node.now_playing.is_some() && !node.is_paused)
+                        .map(|node| *node.key())
+                        .collect::<Vec<_>>()
+                } else {
+                    data.paused_by_all
+                        .lock()
+                        .drain()
+                        .filter(|guild_id| nodes.get(guild_id).map_or(false, |n| n.is_paused))
+                        .collect::<Vec<_>>()
+                }
+            };
+
+            let handles = target_guilds
+                .into_iter()
+                .map(|guild_id| {
+                    let lava_client = lava_client.clone();
+
+                    let handle =
+                        tokio::spawn(async move { lava_client.set_pause(guild_id, pause).await });
+
+                    (guild_id, handle)
+                })
+                .collect::<Vec<_>>();
+
+            let mut affected = Vec::new();
+            let mut failed = HashMap::new();
+
+            for (guild_id, handle) in handles {
+                let result = match handle.await {
+                    Ok(result) => result.map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+
+                match result {
+                    Ok(()) => {
+                        if let Some(mut node) = lava_client.nodes().await.get_mut(&guild_id) {
+                            node.is_paused = pause;
+                        }
+
+                        if pause {
+                            data.paused_by_all.lock().insert(guild_id);
+                        }
+
+                        affected.push(guild_id);
+                    }
+                    Err(e) => {
+                        // Keep it around so it's retried on the next resume.
+                        if !pause {
+                            data.paused_by_all.lock().insert(guild_id);
+                        }
+
+                        failed.insert(guild_id, e);
+                    }
+                }
+            }
+
+            Ok((affected, failed))
+        })
+    }
+
+    /// Pauses every player that is playing, see `set_pause_all()`.
+    #[pyo3(text_signature = "($self, /)")]
+    fn pause_all<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        self.set_pause_all(py, true)
+    }
+
+    /// Resumes every player paused by `pause_all()`, see `set_pause_all()`.
+    #[pyo3(text_signature = "($self, /)")]
+    fn resume_all<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        self.set_pause_all(py, false)
+    }
+
+    /// Jumps to a specific time in the currently playing track.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `time` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<None, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    #[pyo3(text_signature = "($self, guild_id, time, /)")]
+    fn seek_secs<'a>(&self, py: Python<'a>, guild_id: GuildId, time: u64) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            lava_client
+                .seek(guild_id, Duration::from_secs(time))
+                .await
+                .map_err(error::player_error)?;
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Alias to `seek_secs()`
     #[pyo3(text_signature = "($self, guild_id, time, /)")]
     fn jump_to_time_secs<'a>(
         &self,
         py: Python<'a>,
-        guild_id: u64,
+        guild_id: GuildId,
         time: u64,
     ) -> PyResult<&'a PyAny> {
         self.seek_secs(py, guild_id, time)
@@ -444,7 +1740,7 @@ impl Lavalink {
 
     /// Alias to `seek_secs()`
     #[pyo3(text_signature = "($self, guild_id, time, /)")]
-    fn scrub_secs<'a>(&self, py: Python<'a>, guild_id: u64, time: u64) -> PyResult<&'a PyAny> {
+    fn scrub_secs<'a>(&self, py: Python<'a>, guild_id: GuildId, time: u64) -> PyResult<&'a PyAny> {
         self.seek_secs(py, guild_id, time)
     }
 
@@ -456,251 +1752,1459 @@ impl Lavalink {
     /// - `guild_id` : `Unsigned 64 bit integer`
     /// - `time` : `Unsigned 64 bit integer`
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
+    /// Returns: `Future<Result<None, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
     #[pyo3(text_signature = "($self, guild_id, time, /)")]
-    fn seek_millis<'a>(&self, py: Python<'a>, guild_id: u64, time: u64) -> PyResult<&'a PyAny> {
+    fn seek_millis<'a>(&self, py: Python<'a>, guild_id: GuildId, time: u64) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
             lava_client
                 .seek(guild_id, Duration::from_millis(time))
                 .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+                .map_err(error::player_error)?;
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Alias to `seek_millis()`
+    #[pyo3(text_signature = "($self, guild_id, time, /)")]
+    fn jump_to_time_millis<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        time: u64,
+    ) -> PyResult<&'a PyAny> {
+        self.seek_millis(py, guild_id, time)
+    }
+
+    /// Alias to `seek_millis()`
+    #[pyo3(text_signature = "($self, guild_id, time, /)")]
+    fn scrub_millis<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        time: u64,
+    ) -> PyResult<&'a PyAny> {
+        self.seek_millis(py, guild_id, time)
+    }
+
+    /// Restarts the currently playing track, by seeking back to the time it started at.
+    ///
+    /// This only affects the track that is currently playing, if it already finished, it needs
+    /// to be queued again.
+    ///
+    /// Returns `False` if nothing is playing.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<bool, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn restart_track<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let start_time = match lava_client.nodes().await.get(&guild_id) {
+                Some(node) => match &node.now_playing {
+                    Some(track) => track.start_time,
+                    None => return Ok(false),
+                },
+                None => return Ok(false),
+            };
+
+            lava_client
+                .seek(guild_id, Duration::from_millis(start_time))
+                .await
+                .map_err(error::player_error)?;
+
+            Ok(true)
+        })
+    }
+
+    /// Seeks to a percentage of the length of the currently playing track, like for a progress bar.
+    ///
+    /// Returns `False` if nothing is playing, or if the length of the track is not known, like on
+    /// streams or tracks without info.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `percent` : `64 bit float` -- 0 to 100
+    ///
+    /// Returns: `Future<Result<bool, [builtins.ValueError, lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    #[pyo3(text_signature = "($self, guild_id, percent, /)")]
+    fn seek_to_percent<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        percent: f64,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(error::ValueError::new_err(format!(
+                "percent must be between 0 and 100, got {}",
+                percent
+            )));
+        }
+
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let length = lava_client.nodes().await.get(&guild_id).and_then(|node| {
+                let info = node.now_playing.as_ref()?.track.info.as_ref()?;
+
+                if info.is_stream {
+                    None
+                } else {
+                    Some(info.length)
+                }
+            });
+
+            let length = match length {
+                Some(length) => length,
+                None => return Ok(false),
+            };
+
+            let position = (length as f64 * percent / 100.0) as u64;
+
+            lava_client
+                .seek(guild_id, Duration::from_millis(position.min(length)))
+                .await
+                .map_err(error::player_error)?;
+
+            Ok(true)
+        })
+    }
+
+    /// Alias to `restart_track()`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn replay<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        self.restart_track(py, guild_id)
+    }
+
+    /// Sets the volume of the player.
+    /// Max is 1000, min is 0
+    ///
+    /// Raises `ValueError` without sending anything to Lavalink if the volume is above 1000.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `Volume` : `Unsigned 16 bit integer`
+    ///
+    /// Returns: `Future<Result<None, [builtins.ValueError, lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    #[pyo3(text_signature = "($self, guild_id, volume, /)")]
+    fn volume<'a>(&self, py: Python<'a>, guild_id: GuildId, volume: u16) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        validate_volume("volume", volume)?;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            lava_client
+                .volume(guild_id, volume)
+                .await
+                .map_err(error::player_error)?;
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Sets the volume of every player at the same time.
+    ///
+    /// Raises `ValueError` before changing any player if the volume is not between 0 and 1000.
+    ///
+    /// Returns a dict of the guilds that failed, with the error message as the value.
+    ///
+    /// Positional Arguments:
+    /// - `volume` : `Unsigned 16 bit integer` -- 0 to 1000
+    ///
+    /// Returns: `Future<Result<Dict<Unsigned 64 bit integer, String>, builtins.ValueError>>`
+    #[pyo3(text_signature = "($self, volume, /)")]
+    fn set_volume_all<'a>(&self, py: Python<'a>, volume: u16) -> PyResult<&'a PyAny> {
+        validate_volume("volume", volume)?;
+
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let node_guilds = lava_client
+                .nodes()
+                .await
+                .iter()
+                .map(|node| *node.key())
+                .collect::<Vec<_>>();
+
+            let handles = node_guilds
+                .into_iter()
+                .map(|guild_id| {
+                    let lava_client = lava_client.clone();
+
+                    let handle =
+                        tokio::spawn(async move { lava_client.volume(guild_id, volume).await });
+
+                    (guild_id, handle)
+                })
+                .collect::<Vec<_>>();
+
+            let mut failed = HashMap::new();
+
+            for (guild_id, handle) in handles {
+                match handle.await {
+                    Ok(Ok(())) => (),
+                    Ok(Err(e)) => {
+                        failed.insert(guild_id, e.to_string());
+                    }
+                    Err(e) => {
+                        failed.insert(guild_id, e.to_string());
+                    }
+                }
+            }
+
+            Ok(failed)
+        })
+    }
+
+    /// Changes the volume gradually from the current volume to `target`, over `duration_ms`
+    /// milliseconds, in `steps` changes.
+    ///
+    /// Starting another fade on the same guild cancels this one, leaving the volume where it was.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `target` : `Unsigned 16 bit integer` -- 0 to 1000
+    /// - `duration_ms` : `Unsigned 64 bit integer`
+    ///
+    /// Arguments:
+    /// - `steps` : `Unsigned 32 bit integer` -- defaults to 10
+    ///
+    /// Returns: `Future<Result<bool, [builtins.ValueError, lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    /// -- `False` if the fade was cancelled
+    #[pyo3(text_signature = "($self, guild_id, target, duration_ms, /, steps=10)")]
+    #[args(steps = "10")]
+    fn fade_volume<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        target: u16,
+        duration_ms: u64,
+        steps: u32,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+
+        validate_volume("target", target)?;
+
+        let lava_client = self.lava.clone();
+        let data = self.data.clone();
+        let steps = steps.max(1);
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let fade_id = data.fades.start(guild_id);
+
+            let start = lava_client
+                .nodes()
+                .await
+                .get(&guild_id)
+                .map_or(100, |node| node.volume);
+
+            let step_duration = Duration::from_millis(duration_ms / steps as u64);
+
+            for step in 1..=steps {
+                let volume =
+                    start as i64 + (target as i64 - start as i64) * step as i64 / steps as i64;
+
+                if !data.fades.is_current(guild_id, fade_id) {
+                    return Ok(false);
+                }
+
+                if let Err(e) = lava_client.volume(guild_id, volume as u16).await {
+                    data.fades.finish(guild_id, fade_id);
+                    return Err(error::player_error(e));
+                }
+
+                if step != steps {
+                    sleep(step_duration).await;
+                }
+            }
+
+            data.fades.finish(guild_id, fade_id);
+
+            Ok(true)
+        })
+    }
+
+    /// Sets all equalizer levels.
+    ///
+    /// - There are 15 bands (0-14) that can be changed.
+    /// - The floating point value is the multiplier for the given band.
+    /// - The default value is 0.
+    /// - Valid values range from -0.25 to 1.0, where -0.25 means the given band is completely muted, and 0.25 means it is doubled.
+    /// - Modifying the gain could also change the volume of the output.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `bands` : `List<64 bit floating point>` -- Must be 15 in length
+    ///
+    /// Raises `ValueError` before sending anything if a band is not between 0 and 14, or a gain is
+    /// not between -0.25 and 1.0.
+    ///
+    /// Returns: `Future<Result<None, [builtins.ValueError, lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    #[pyo3(text_signature = "($self, guild_id, bands, /)")]
+    fn equalize_all<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        bands: [f64; 15],
+    ) -> PyResult<&'a PyAny> {
+        for (band, gain) in bands.iter().enumerate() {
+            validate_band(band as u8, *gain)?;
+        }
+
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            lava_client
+                .equalize_all(guild_id, bands)
+                .await
+                .map_err(error::player_error)?;
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Equalize a dynamic set of bands, rather than just one or all of them at once.
+    ///
+    /// Unmentioned bands will remain unmodified.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `bands` : `List<64 bit floating point>` -- Must be 15 or less in length
+    ///
+    /// Raises `ValueError` before sending anything if a band is not between 0 and 14, or a gain is
+    /// not between -0.25 and 1.0.
+    ///
+    /// Returns: `Future<Result<None, [builtins.ValueError, lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    #[pyo3(text_signature = "($self, guild_id, bands, /)")]
+    fn equalize_dynamic<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        bands: Vec<Band>,
+    ) -> PyResult<&'a PyAny> {
+        for band in &bands {
+            validate_band(band.inner.band, band.inner.gain)?;
+        }
+
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            lava_client
+                .equalize_dynamic(guild_id, bands.iter().map(|i| i.inner.clone()).collect())
+                .await
+                .map_err(error::player_error)?;
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Equalizes a specific band.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `band` : `Band`
+    ///
+    /// Raises `ValueError` before sending anything if a band is not between 0 and 14, or a gain is
+    /// not between -0.25 and 1.0.
+    ///
+    /// Returns: `Future<Result<None, [builtins.ValueError, lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    #[pyo3(text_signature = "($self, guild_id, band, /)")]
+    fn equalize_band<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        band: Band,
+    ) -> PyResult<&'a PyAny> {
+        validate_band(band.inner.band, band.inner.gain)?;
+
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            lava_client
+                .equalize_band(guild_id, band.inner)
+                .await
+                .map_err(error::player_error)?;
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Resets all equalizer levels.
+    ///
+    /// This can raise an exception if a network error happens.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<None, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn equalize_reset<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            lava_client
+                .equalize_reset(guild_id)
+                .await
+                .map_err(error::player_error)?;
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Returns a page of the queue of a guild, without copying the rest of the queue, to show big
+    /// queues in pages.
+    ///
+    /// Pages start at 0, and a page past the last one returns the last page. Only the upcoming
+    /// tracks are paged: while a track is playing, it's the first one of `Node.queue`, and it's left
+    /// out, use `Node.now_playing` for it.
+    ///
+    /// ```py
+    /// page = await lavalink.get_queue_page(guild_id, page_number, 10)
+    /// lines = [track.track.info.title for track in page.tracks]
+    /// footer = f"Page {page.page + 1}/{page.total_pages} - {page.total_tracks} tracks"
+    /// ```
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `page` : `Unsigned 64 bit integer`
+    /// - `per_page` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<QueuePage, [builtins.ValueError]>>`
+    #[pyo3(text_signature = "($self, guild_id, page, per_page, /)")]
+    fn get_queue_page<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        page: usize,
+        per_page: usize,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        if per_page == 0 {
+            return Err(error::ValueError::new_err(
+                "per_page must be greater than 0",
+            ));
+        }
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let nodes = lava_client.nodes().await;
+            let node = nodes.get(&guild_id);
+            let queue = node.as_ref().map_or(&[][..], |node| {
+                // While playing, `queue[0]` is the current track.
+                let start = if node.now_playing.is_some() {
+                    node.queue.len().min(1)
+                } else {
+                    0
+                };
+
+                &node.queue[start..]
+            });
+
+            let total_tracks = queue.len();
+            let total_pages = ((total_tracks + per_page - 1) / per_page).max(1);
+            let page = page.min(total_pages - 1);
+
+            Ok(QueuePage {
+                tracks: queue
+                    .iter()
+                    .skip(page * per_page)
+                    .take(per_page)
+                    .cloned()
+                    .collect(),
+                page,
+                total_tracks,
+                total_pages,
+            })
+        })
+    }
+
+    /// Saves the queue of a guild as a list of dicts that only contain JSON compatible values, so it
+    /// can be stored and restored later with `load_queue()`.
+    ///
+    /// Each dict has the keys `track` (base 64 track), `start_time`, `end_time` and `requester`.
+    /// The track info is not saved, but it can be obtained again with `decode_track()`.
+    ///
+    /// The currently playing track is the first item of the queue, so it's saved as the first item
+    /// too, unless `include_now_playing` is `False`.
+    ///
+    /// Returns an empty list if the guild doesn't have a Node.
+    ///
+    /// ```py
+    /// lavalink: lavasnek_rs.Lavalink = ...
+    ///
+    /// queue = await lavalink.save_queue(guild_id)
+    /// with open(f"{guild_id}.json", "w") as f:
+    ///     json.dump(queue, f)
+    ///
+    /// # After restarting and creating the session again.
+    /// with open(f"{guild_id}.json") as f:
+    ///     await lavalink.load_queue(guild_id, json.load(f))
+    /// ```
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Arguments:
+    /// - `include_now_playing` : `bool` -- defaults to True
+    ///
+    /// Returns: `Future<List<Dict<String, Any>>>`
+    #[pyo3(text_signature = "($self, guild_id, /, include_now_playing=True)")]
+    #[args(include_now_playing = "true")]
+    fn save_queue<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        include_now_playing: bool,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let tracks = match lava_client.nodes().await.get(&guild_id) {
+                Some(node) => {
+                    // While playing, `queue[0]` is the current track.
+                    let skip = if node.now_playing.is_some() && !include_now_playing {
+                        1
+                    } else {
+                        0
+                    };
+
+                    node.queue.iter().skip(skip).cloned().collect::<Vec<_>>()
+                }
+                None => vec![],
+            };
+
+            Python::with_gil(|py| {
+                tracks
+                    .iter()
+                    .map(|track_queue| track_queue_to_dict(py, track_queue))
+                    .collect::<PyResult<Vec<_>>>()
+            })
+        })
+    }
+
+    /// Restores a queue saved with `save_queue()` to the end of the guild queue.
+    ///
+    /// If `start` is `True`, the tracks are queued like `PlayBuilder.queue()` does, starting the
+    /// queue loop if it's not running. Otherwise, they are only added to the Node.
+    ///
+    /// Needs for `Lavalink.create_session() to be called first.
+    ///
+    /// Can raise `TypeError` if an item doesn't have a `track` key, or any of the values has the
+    /// wrong type, and `ValueError` if an item has an `end_time` that is not greater than its
+    /// `start_time`.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `queue` : `List<Dict<String, Any>>`
+    ///
+    /// Arguments:
+    /// - `start` : `bool` -- defaults to True
+    ///
+    /// Returns: `Future<Result<Unsigned 64 bit integer, [builtins.TypeError, builtins.ValueError, lavasnek_rs.NoSessionPresent, lavasnek_rs.NetworkError, lavasnek_rs.QueueError]>>`
+    /// -- the number of tracks added
+    #[pyo3(text_signature = "($self, guild_id, queue, /, start=True)")]
+    #[args(start = "true")]
+    fn load_queue<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        queue: Vec<&PyDict>,
+        start: bool,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+        let tracks = queue
+            .into_iter()
+            .map(track_queue_from_dict)
+            .collect::<PyResult<Vec<_>>>()?;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            if !lava_client.nodes().await.contains_key(&guild_id) {
+                return Err(error::NoSessionPresent::new_err(
+                    LavalinkError::NoSessionPresent.to_string(),
+                ));
+            }
+
+            let count = tracks.len();
+
+            if start {
+                for track_queue in tracks {
+                    let mut builder = lava_client.play(guild_id, track_queue.track);
+                    builder.start = track_queue.start_time;
+                    builder.finish = track_queue.end_time.unwrap_or_default();
+                    builder.requester = track_queue.requester;
+
+                    builder.queue().await.map_err(error::queue_error)?;
+                }
+            } else if let Some(mut node) = lava_client.nodes().await.get_mut(&guild_id) {
+                node.queue.extend(tracks);
+            }
+
+            Ok(count)
+        })
+    }
+
+    /// Removes the tracks of the queue that are already earlier in the queue, keeping the first
+    /// occurrence. The currently playing track is never removed.
+    ///
+    /// `by` chooses what makes 2 tracks the same:
+    /// - `"track"` : the exact base 64 track.
+    /// - `"uri"` : the track uri, so the same song loaded from different searches is also removed.
+    /// Tracks without info fall back to the base 64 track.
+    ///
+    /// Returns 0 if the guild doesn't have a Node.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Arguments:
+    /// - `by` : `String` -- defaults to "track"
+    ///
+    /// Returns: `Future<Result<Unsigned 64 bit integer, builtins.ValueError>>` -- the number of
+    /// tracks removed
+    #[pyo3(text_signature = "($self, guild_id, /, by=\"track\")")]
+    #[args(by = "\"track\"")]
+    fn remove_duplicates<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        by: &str,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let by_uri = match by {
+            "track" => false,
+            "uri" => true,
+            _ => {
+                return Err(error::ValueError::new_err(format!(
+                    "`by` must be \"track\" or \"uri\", got {:?}",
+                    by
+                )))
+            }
+        };
+
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let nodes = lava_client.nodes().await;
+            let mut node = match nodes.get_mut(&guild_id) {
+                Some(node) => node,
+                None => return Ok(0),
+            };
+
+            let mut seen = HashSet::new();
+            let before = node.queue.len();
+
+            node.queue.retain(|track_queue| {
+                let track = &track_queue.track;
+
+                let key = match &track.info {
+                    Some(info) if by_uri => info.uri.clone(),
+                    _ => track.track.clone(),
+                };
+
+                seen.insert(key)
+            });
+
+            Ok(before - node.queue.len())
+        })
+    }
+
+    /// Changes the user who requested a track of the queue, or of the currently playing track if
+    /// `index` is `None`. `user_id` can be `None` to remove the requester.
+    ///
+    /// When the currently playing track is also the first track of the queue, like with the queue
+    /// loop, both `index` 0 and `None` change `Node.now_playing` and `Node.queue[0]` together.
+    /// Tracks started with `PlayBuilder.start()` are not in the queue, so only one of them changes.
+    ///
+    /// Raises `NoSessionPresent` if the guild doesn't have a Node, and `IndexError` if there's no
+    /// track at the index, or nothing is playing when `index` is `None`.
+    ///
+    /// ```py
+    /// # Gift the currently playing track.
+    /// await lavalink.set_requester(guild_id, None, friend_id)
+    /// ```
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `index` : `Optional Unsigned 64 bit integer`
+    /// - `user_id` : `Optional Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<None, [lavasnek_rs.NoSessionPresent, builtins.IndexError]>>`
+    #[pyo3(text_signature = "($self, guild_id, index, user_id, /)")]
+    fn set_requester<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        index: Option<usize>,
+        user_id: Option<UserId>,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            {
+                let nodes = lava_client.nodes().await;
+                let mut node = nodes.get_mut(&guild_id).ok_or_else(|| {
+                    error::NoSessionPresent::new_err(LavalinkError::NoSessionPresent.to_string())
+                })?;
+
+                let requester = user_id.map(|id| id.0.into());
+
+                // The queue loop keeps the current track as `queue[0]`, but tracks started with
+                // `PlayBuilder.start()` are only in `now_playing`.
+                let first_is_now_playing = match (&node.now_playing, node.queue.first()) {
+                    (Some(now_playing), Some(first)) => {
+                        now_playing.track.track == first.track.track
+                    }
+                    _ => false,
+                };
+
+                let track_queue = match index {
+                    Some(index) => node.queue.get_mut(index),
+                    None => node.now_playing.as_mut(),
+                }
+                .ok_or_else(|| match index {
+                    Some(_) => error::IndexError::new_err("Queue index out of range"),
+                    None => error::IndexError::new_err("Nothing is playing"),
+                })?;
+
+                track_queue.requester = requester;
+
+                if index.unwrap_or(0) == 0 && first_is_now_playing {
+                    if let Some(now_playing) = node.now_playing.as_mut() {
+                        now_playing.requester = requester;
+                    }
+
+                    if let Some(first) = node.queue.first_mut() {
+                        first.requester = requester;
+                    }
+                }
+            }
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Puts the last track that finished playing, or was skipped, back at the front of the queue,
+    /// right after the current track if something is playing, so it plays next, and returns it.
+    ///
+    /// The last 10 tracks of each guild are remembered, so calling this again goes further back.
+    /// Returns `None` if there are no more tracks to go back to.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<Option<TrackQueue>, [lavasnek_rs.NoSessionPresent]>>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn previous<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+        let data = self.data.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let nodes = lava_client.nodes().await;
+            let mut node = nodes.get_mut(&guild_id).ok_or_else(|| {
+                error::NoSessionPresent::new_err(LavalinkError::NoSessionPresent.to_string())
+            })?;
+
+            let track_queue = data.previous_tracks.pop(guild_id);
+
+            if let Some(track_queue) = &track_queue {
+                // While playing, `queue[0]` is the current track.
+                let index = if node.now_playing.is_some() {
+                    node.queue.len().min(1)
+                } else {
+                    0
+                };
+
+                node.queue.insert(index, track_queue.clone());
+            }
+
+            Ok(track_queue.map(|inner| TrackQueue { inner }))
+        })
+    }
+
+    /// Enables or disables autoplay on the guild.
+    ///
+    /// With autoplay enabled, when a track finishes playing and the queue is empty, a related
+    /// track is queued, and the `autoplay_track` event is sent. YouTube tracks use the YouTube mix
+    /// of the track, Spotify tracks use the recommendations of the LavaSrc plugin, and tracks from
+    /// other sources are searched on YouTube first. Recently played tracks are skipped.
+    ///
+    /// Disabling it lets the tracks already in the queue finish normally.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `enabled` : `bool`
+    ///
+    /// Returns: `None`
+    #[pyo3(text_signature = "($self, guild_id, enabled, /)")]
+    fn autoplay(&self, guild_id: GuildId, enabled: bool) {
+        let mut autoplay = self.data.autoplay.lock();
+
+        if enabled {
+            autoplay.insert(guild_id.0);
+        } else {
+            autoplay.remove(&guild_id.0);
+        }
+    }
+
+    /// Returns the last tracks that finished playing on the guild, newest first.
+    ///
+    /// Only tracks that played until the end are included, not skipped or stopped ones. The amount
+    /// of tracks remembered is set with `LavalinkBuilder.set_history_size()`, and the history is
+    /// cleared by `flush_guild()`.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `List<TrackQueue>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn get_history(&self, guild_id: GuildId) -> Vec<TrackQueue> {
+        self.data
+            .history
+            .get(guild_id.0)
+            .into_iter()
+            .map(|inner| TrackQueue { inner })
+            .collect()
+    }
+
+    /// Removes every track requested by a user from the queue.
+    ///
+    /// The currently playing track is kept even if the user requested it, unless
+    /// `include_now_playing` is `True`, in which case it's skipped.
+    ///
+    /// Returns 0 if the guild doesn't have a Node.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `user_id` : `Unsigned 64 bit integer`
+    ///
+    /// Arguments:
+    /// - `include_now_playing` : `bool` -- defaults to False
+    ///
+    /// This can raise an exception if a network error happens while skipping.
+    ///
+    /// Returns: `Future<Result<Unsigned 64 bit integer, [lavasnek_rs.NetworkError, lavasnek_rs.PlayerError]>>`
+    /// -- the number of tracks removed
+    #[pyo3(text_signature = "($self, guild_id, user_id, /, include_now_playing=False)")]
+    #[args(include_now_playing = "false")]
+    fn remove_user_tracks<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        user_id: UserId,
+        include_now_playing: bool,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let user_id = user_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let (removed, skip_now_playing) = {
+                let nodes = lava_client.nodes().await;
+                let mut node = match nodes.get_mut(&guild_id) {
+                    Some(node) => node,
+                    None => return Ok(0),
+                };
+
+                let is_requester = |track_queue: &LavaTrackQueue| {
+                    track_queue.requester.map(|u| u.0) == Some(user_id)
+                };
+
+                // While playing, `queue[0]` is the current track, which is only removed by
+                // skipping it.
+                let start = if node.now_playing.is_some() {
+                    node.queue.len().min(1)
+                } else {
+                    0
+                };
+
+                let mut upcoming = node.queue.split_off(start);
+                let before = upcoming.len();
+                upcoming.retain(|track_queue| !is_requester(track_queue));
+                let removed = before - upcoming.len();
+                node.queue.extend(upcoming);
+
+                let skip_now_playing =
+                    include_now_playing && node.now_playing.as_ref().map_or(false, is_requester);
+
+                (removed, skip_now_playing)
+            };
+
+            if !skip_now_playing {
+                return Ok(removed);
+            }
+
+            if lava_client.skip(guild_id).await.is_none() {
+                lava_client
+                    .stop(guild_id)
+                    .await
+                    .map_err(error::player_error)?;
+            }
+
+            Ok(removed + 1)
+        })
+    }
+
+    /// Remove the guild from the queue loops.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<None>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn remove_guild_from_loops<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            lava_client.loops().await.remove(&guild_id);
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Remove the guild node.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<None>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn remove_guild_node<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            lava_client.nodes().await.remove(&guild_id);
+
+            Ok(Python::with_gil(|py| py.None()))
+        })
+    }
+
+    /// Get the current guild from the queue nodes.
+    ///
+    /// This returns a clone of the node, so modifying it won't change the real node, for that you
+    /// will need to re-set the new node with `set_guild_node()`.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Option<Node>>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn get_guild_node<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let nodes = lava_client.nodes().await;
+            let node = nodes.get(&guild_id);
+
+            Ok(Python::with_gil(|py| {
+                if let Some(node) = node {
+                    Node {
+                        inner: node.clone(),
+                    }
+                    .into_py(py)
+                } else {
+                    py.None()
+                }
+            }))
+        })
+    }
+
+    /// Like `get_guild_node()`, but for many guilds at once, locking the nodes only once.
+    ///
+    /// Returns a dict of the guilds that have a Node, the ones that don't are left out.
+    ///
+    /// Positional Arguments:
+    /// - `guild_ids` : `List<Unsigned 64 bit integer>`
+    ///
+    /// Returns: `Future<Dict<Unsigned 64 bit integer, Node>>`
+    #[pyo3(text_signature = "($self, guild_ids, /)")]
+    fn get_guild_nodes<'a>(&self, py: Python<'a>, guild_ids: Vec<GuildId>) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let nodes = lava_client.nodes().await;
+
+            let guild_nodes = guild_ids
+                .into_iter()
+                .filter_map(|guild_id| {
+                    nodes.get(&guild_id.0).map(|node| {
+                        let node = Node {
+                            inner: node.clone(),
+                        };
 
-            Ok(Python::with_gil(|py| py.None()))
+                        (guild_id.0, node)
+                    })
+                })
+                .collect::<HashMap<_, _>>();
+
+            Ok(guild_nodes)
         })
     }
 
-    /// Alias to `seek_millis()`
-    #[pyo3(text_signature = "($self, guild_id, time, /)")]
-    fn jump_to_time_millis<'a>(
-        &self,
-        py: Python<'a>,
-        guild_id: u64,
-        time: u64,
-    ) -> PyResult<&'a PyAny> {
-        self.seek_millis(py, guild_id, time)
-    }
+    /// Like `get_guild_node()`, but without awaiting, for places where that's not possible, like a
+    /// synchronous property.
+    ///
+    /// This is best-effort: if the nodes, or the guild's Node, are locked by something else at the
+    /// time, `None` is returned right away even if the guild has a Node.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Option<Node>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn try_get_guild_node(&self, guild_id: GuildId) -> Option<Node> {
+        let nodes = self.lava.nodes().now_or_never()?;
+        let node = nodes.try_get(&guild_id.0).try_unwrap()?;
 
-    /// Alias to `seek_millis()`
-    #[pyo3(text_signature = "($self, guild_id, time, /)")]
-    fn scrub_millis<'a>(&self, py: Python<'a>, guild_id: u64, time: u64) -> PyResult<&'a PyAny> {
-        self.seek_millis(py, guild_id, time)
+        Some(Node {
+            inner: node.clone(),
+        })
     }
 
-    /// Sets the volume of the player.
-    /// Max is 1000, min is 0
+    /// Returns the data stored on the Node of the guild, like `Node.get_data()`, without copying
+    /// the rest of the Node, or `None` if the guild doesn't have a Node.
     ///
-    /// This can raise an exception if a network error happens.
+    /// If no data has been set yet, it will default to a Dict.
     ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
-    /// - `Volume` : `Unsigned 16 bit integer`
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
-    #[pyo3(text_signature = "($self, guild_id, volume, /)")]
-    fn volume<'a>(&self, py: Python<'a>, guild_id: u64, volume: u16) -> PyResult<&'a PyAny> {
+    /// Returns: `Future<Option<T>>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn get_node_data<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            lava_client
-                .volume(guild_id, volume)
+            // The Node lock is released before taking the GIL.
+            let node = lava_client
+                .nodes()
                 .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+                .get(&guild_id)
+                .map(|node| node.clone());
 
-            Ok(Python::with_gil(|py| py.None()))
+            Ok(Python::with_gil(|py| {
+                node.map_or_else(|| py.None(), |node| node_data(py, &node))
+            }))
         })
     }
 
-    /// Sets all equalizer levels.
-    ///
-    /// - There are 15 bands (0-14) that can be changed.
-    /// - The floating point value is the multiplier for the given band.
-    /// - The default value is 0.
-    /// - Valid values range from -0.25 to 1.0, where -0.25 means the given band is completely muted, and 0.25 means it is doubled.
-    /// - Modifying the gain could also change the volume of the output.
-    ///
-    /// This can raise an exception if a network error happens.
+    /// Sets the data stored on the Node of the guild, like `Node.set_data()`, without having to
+    /// get and set the whole Node.
     ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
-    /// - `bands` : `List<64 bit floating point>` -- Must be 15 in length
+    /// - `data` : `T`
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
-    #[pyo3(text_signature = "($self, guild_id, bands, /)")]
-    fn equalize_all<'a>(
+    /// Returns: `Future<Result<None, lavasnek_rs.NoSessionPresent>>`
+    #[pyo3(text_signature = "($self, guild_id, data, /)")]
+    fn set_node_data<'a>(
         &self,
         py: Python<'a>,
-        guild_id: u64,
-        bands: [f64; 15],
+        guild_id: GuildId,
+        data: PyObject,
     ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            lava_client
-                .equalize_all(guild_id, bands)
-                .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+            {
+                let nodes = lava_client.nodes().await;
+                let node = nodes.get(&guild_id).ok_or_else(|| {
+                    error::NoSessionPresent::new_err(LavalinkError::NoSessionPresent.to_string())
+                })?;
+
+                set_node_data(&node, data);
+            }
 
             Ok(Python::with_gil(|py| py.None()))
         })
     }
 
-    /// Equalize a dynamic set of bands, rather than just one or all of them at once.
-    ///
-    /// Unmentioned bands will remain unmodified.
+    /// Like `get_guild_node()`, but if the guild doesn't have a Node, a new default one is returned
+    /// instead of `None`, with an empty queue, volume 100 and not paused.
     ///
-    /// This can raise an exception if a network error happens.
+    /// The default Node is not stored, so modifying it doesn't affect the guild. Use
+    /// `create_session()` or `set_guild_node()` for that.
     ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
-    /// - `bands` : `List<64 bit floating point>` -- Must be 15 or less in length
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
-    #[pyo3(text_signature = "($self, guild_id, bands, /)")]
-    fn equalize_dynamic<'a>(
+    /// Returns: `Future<Node>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn get_guild_node_or_default<'a>(
         &self,
         py: Python<'a>,
-        guild_id: u64,
-        bands: Vec<Band>,
+        guild_id: GuildId,
     ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            lava_client
-                .equalize_dynamic(guild_id, bands.iter().map(|i| i.inner.clone()).collect())
+            let node = match lava_client.nodes().await.get(&guild_id) {
+                Some(node) => node.clone(),
+                None => LavaNode {
+                    guild: guild_id.into(),
+                    volume: 100,
+                    ..Default::default()
+                },
+            };
+
+            Ok(Node { inner: node })
+        })
+    }
+
+    /// Get the IDs of every guild that currently has a Node.
+    ///
+    /// Returns: `Future<List<Unsigned 64 bit integer>>`
+    #[pyo3(text_signature = "($self, /)")]
+    fn active_guilds<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let lava_client = self.lava.clone();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let guilds = lava_client
+                .nodes()
                 .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+                .iter()
+                .map(|node| *node.key())
+                .collect::<Vec<_>>();
 
-            Ok(Python::with_gil(|py| py.None()))
+            Ok(guilds)
         })
     }
 
-    /// Equalizes a specific band.
-    ///
-    /// This can raise an exception if a network error happens.
+    /// Get the IDs of every guild that has a Node with a track currently playing.
     ///
-    /// Positional Arguments:
-    /// - `guild_id` : `Unsigned 64 bit integer`
-    /// - `band` : `Band`
+    /// Paused tracks are still considered to be playing.
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
-    #[pyo3(text_signature = "($self, guild_id, band, /)")]
-    fn equalize_band<'a>(&self, py: Python<'a>, guild_id: u64, band: Band) -> PyResult<&'a PyAny> {
+    /// Returns: `Future<List<Unsigned 64 bit integer>>`
+    #[pyo3(text_signature = "($self, /)")]
+    fn playing_guilds<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            lava_client
-                .equalize_band(guild_id, band.inner)
+            let guilds = lava_client
+                .nodes()
                 .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+                .iter()
+                .filter(|node| node.now_playing.is_some())
+                .map(|node| *node.key())
+                .collect::<Vec<_>>();
 
-            Ok(Python::with_gil(|py| py.None()))
+            Ok(guilds)
         })
     }
 
-    /// Resets all equalizer levels.
+    /// Returns when the current track of the guild started playing, in milliseconds since the unix
+    /// epoch, or `None` if nothing is playing.
     ///
-    /// This can raise an exception if a network error happens.
+    /// The time is set when the `track_start` event is received, and cleared on `track_finish`
+    /// and `player_destroyed`. Pausing, resuming and seeking don't change it, so subtract the time
+    /// spent paused if that's needed for an elapsed time display.
     ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
     ///
-    /// Returns: `Future<Result<None, lavasnek_rs.NetworkError>>`
+    /// Returns: `Option<Unsigned 64 bit integer>`
     #[pyo3(text_signature = "($self, guild_id, /)")]
-    fn equalize_reset<'a>(&self, py: Python<'a>, guild_id: u64) -> PyResult<&'a PyAny> {
-        let lava_client = self.lava.clone();
+    fn playing_since(&self, guild_id: GuildId) -> Option<u64> {
+        self.data.playing_since.lock().get(&guild_id.0).copied()
+    }
 
-        pyo3_asyncio::tokio::future_into_py(py, async move {
-            lava_client
-                .equalize_reset(guild_id)
-                .await
-                .map_err(|e| error::NetworkError::new_err(e.to_string()))?;
+    /// Returns the delay of the last `player_update` event of the guild, in milliseconds, or `None`
+    /// if no update was received since the player was created.
+    ///
+    /// This is `PlayerUpdate.state_ping_ms`: the time between Lavalink sending the update and it
+    /// being received, so a value that keeps growing means the player is lagging behind. It includes
+    /// the difference between the clocks of both machines, so it's only accurate if they are synced.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Option<Signed 64 bit integer>`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn player_latency(&self, guild_id: GuildId) -> Option<i64> {
+        self.data.latencies.lock().get(&guild_id.0).copied()
+    }
 
-            Ok(Python::with_gil(|py| py.None()))
-        })
+    /// Returns how many times the current track of the guild started again right after itself,
+    /// like when it's on repeat by queueing it again on `track_finish`.
+    ///
+    /// It's 0 the first time a track plays, and it's reset when a different track starts.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Unsigned 32 bit integer`
+    #[pyo3(text_signature = "($self, guild_id, /)")]
+    fn loop_count(&self, guild_id: GuildId) -> u32 {
+        self.data
+            .loop_counts
+            .lock()
+            .get(&guild_id.0)
+            .map_or(0, |(_, count)| *count)
     }
 
-    /// Remove the guild from the queue loops.
+    /// Returns the info of the currently playing track of the guild, or `None` if nothing is
+    /// playing.
+    ///
+    /// If the track doesn't have its info, it's decoded, and the info is saved on the node so the
+    /// next calls don't need to decode it again.
+    ///
+    /// This can raise an exception if a network error happens.
     ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
     ///
-    /// Returns: `Future<None>`
+    /// Returns: `Future<Result<Option<Info>, [lavasnek_rs.NetworkError, builtins.TimeoutError]>>`
     #[pyo3(text_signature = "($self, guild_id, /)")]
-    fn remove_guild_from_loops<'a>(&self, py: Python<'a>, guild_id: u64) -> PyResult<&'a PyAny> {
+    fn current_track_info<'a>(&self, py: Python<'a>, guild_id: GuildId) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
+        let rest_timeout = self.data.config.rest_timeout;
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            lava_client.loops().await.remove(&guild_id);
+            let track = match lava_client
+                .nodes()
+                .await
+                .get(&guild_id)
+                .and_then(|node| node.now_playing.as_ref().map(|t| t.track.clone()))
+            {
+                Some(track) => track,
+                None => return Ok(None),
+            };
+
+            if let Some(info) = track.info {
+                return Ok(Some(Info { inner: info }));
+            }
+
+            // The node is not locked while decoding, so the info is only saved if the same track
+            // is still playing.
+            let info =
+                rest_with_timeout(rest_timeout, lava_client.decode_track(track.track.clone()))
+                    .await?;
+
+            if let Some(mut node) = lava_client.nodes().await.get_mut(&guild_id) {
+                if let Some(now_playing) = node
+                    .now_playing
+                    .as_mut()
+                    .filter(|now_playing| now_playing.track.track == track.track)
+                {
+                    now_playing.track.info = Some(info.clone());
+                }
+            }
 
-            Ok(Python::with_gil(|py| py.None()))
+            Ok(Some(Info { inner: info }))
         })
     }
 
-    /// Remove the guild node.
+    /// Set the node of a guild with a new one.
     ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `node` : `Node`
     ///
     /// Returns: `Future<None>`
-    #[pyo3(text_signature = "($self, guild_id, /)")]
-    fn remove_guild_node<'a>(&self, py: Python<'a>, guild_id: u64) -> PyResult<&'a PyAny> {
+    #[pyo3(text_signature = "($self, guild_id, node, /)")]
+    fn set_guild_node<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        node: Node,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            lava_client.nodes().await.remove(&guild_id);
+            let nodes = lava_client.nodes().await;
+            nodes.insert(guild_id, node.inner);
 
             Ok(Python::with_gil(|py| py.None()))
         })
     }
 
-    /// Get the current guild from the queue nodes.
+    /// Moves the Node of a guild, with its queue and data, to another guild, like when migrating
+    /// between shards.
     ///
-    /// This returns a clone of the node, so modifying it won't change the real node, for that you
-    /// will need to re-set the new node with `set_guild_node()`.
+    /// Only the Node is moved, the voice connections, the Lavalink session and the running event
+    /// loops are not changed.
+    ///
+    /// Raises `NoSessionPresent` if `src_guild` doesn't have a Node, and `ValueError` if `dst_guild`
+    /// already has one, unless `overwrite` is `True`.
     ///
     /// Positional Arguments:
-    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `src_guild` : `Unsigned 64 bit integer`
+    /// - `dst_guild` : `Unsigned 64 bit integer`
     ///
-    /// Returns: `Future<Option<Node>>`
-    #[pyo3(text_signature = "($self, guild_id, /)")]
-    fn get_guild_node<'a>(&self, py: Python<'a>, guild_id: u64) -> PyResult<&'a PyAny> {
+    /// Arguments:
+    /// - `overwrite` : `bool` -- defaults to False
+    ///
+    /// Returns: `Future<Result<None, [lavasnek_rs.NoSessionPresent, builtins.ValueError]>>`
+    #[pyo3(text_signature = "($self, src_guild, dst_guild, /, overwrite=False)")]
+    #[args(overwrite = "false")]
+    fn transfer_node<'a>(
+        &self,
+        py: Python<'a>,
+        src_guild: GuildId,
+        dst_guild: GuildId,
+        overwrite: bool,
+    ) -> PyResult<&'a PyAny> {
+        let src_guild = src_guild.0;
+        let dst_guild = dst_guild.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
             let nodes = lava_client.nodes().await;
-            let node = nodes.get(&guild_id);
 
-            Ok(Python::with_gil(|py| {
-                if let Some(node) = node {
-                    Node {
-                        inner: node.clone(),
-                    }
-                    .into_py(py)
-                } else {
-                    py.None()
-                }
-            }))
+            if !nodes.contains_key(&src_guild) {
+                return Err(error::NoSessionPresent::new_err(
+                    LavalinkError::NoSessionPresent.to_string(),
+                ));
+            }
+
+            if src_guild == dst_guild {
+                return Ok(Python::with_gil(|py| py.None()));
+            }
+
+            if !overwrite && nodes.contains_key(&dst_guild) {
+                return Err(error::ValueError::new_err(format!(
+                    "The guild {} already has a Node",
+                    dst_guild
+                )));
+            }
+
+            if let Some((_, mut node)) = nodes.remove(&src_guild) {
+                node.guild.0 = dst_guild;
+                nodes.insert(dst_guild, node);
+            }
+
+            Ok(Python::with_gil(|py| py.None()))
         })
     }
 
-    /// Set the node of a guild with a new one.
+    /// Modifies the Node of the guild with a callback, without losing changes made by the queue
+    /// loop or other tasks in between, like it can happen between `get_guild_node()` and
+    /// `set_guild_node()`.
+    ///
+    /// The callback receives a copy of the `Node`, and the changes made to it are saved after it
+    /// returns. If the Node, including the object set with `set_data()`, changed while the callback
+    /// was running, the callback is called again with the new Node, up to 10 times before raising
+    /// `RuntimeError`, so this can fail if the Node is being modified constantly, like while the
+    /// queue is changed in a loop. If the callback raises an exception, nothing is saved.
+    ///
+    /// The callback must be a regular function, not a coroutine, and it should only modify the
+    /// Node it receives: calling other `Lavalink` methods from inside it is not allowed.
+    ///
+    /// Returns `False` without calling the callback if the guild doesn't have a Node.
+    ///
+    /// ```py
+    /// def shuffle(node):
+    ///     queue = node.queue
+    ///     random.shuffle(queue)
+    ///     node.queue = queue
+    ///
+    /// await lavalink.update_node(guild_id, shuffle)
+    /// ```
     ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
-    /// - `node` : `Node`
+    /// - `callback` : `Callable<[Node], None>`
     ///
-    /// Returns: `Future<None>`
-    #[pyo3(text_signature = "($self, guild_id, node, /)")]
-    fn set_guild_node<'a>(&self, py: Python<'a>, guild_id: u64, node: Node) -> PyResult<&'a PyAny> {
+    /// Returns: `Future<Result<bool, [builtins.RuntimeError, Exception]>>` -- or any exception
+    /// raised by the callback
+    #[pyo3(text_signature = "($self, guild_id, callback, /)")]
+    fn update_node<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        callback: PyObject,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
             let nodes = lava_client.nodes().await;
-            nodes.insert(guild_id, node.inner);
 
-            Ok(Python::with_gil(|py| py.None()))
+            for _ in 0..UPDATE_NODE_ATTEMPTS {
+                // No guard is held while the callback runs, as it needs the GIL.
+                let snapshot = match nodes.get(&guild_id) {
+                    Some(node) => node.clone(),
+                    None => return Ok(false),
+                };
+                let data_before = node_data_id(&snapshot);
+
+                let updated = Python::with_gil(|py| -> PyResult<LavaNode> {
+                    let py_node = Py::new(
+                        py,
+                        Node {
+                            inner: snapshot.clone(),
+                        },
+                    )?;
+
+                    callback.call1(py, (py_node.clone_ref(py),))?;
+
+                    let updated = py_node.borrow(py).inner.clone();
+                    Ok(updated)
+                })?;
+
+                let mut node = match nodes.get_mut(&guild_id) {
+                    Some(node) => node,
+                    None => return Ok(false),
+                };
+
+                // The data may be shared between copies of the Node, in which case data set by the
+                // callback is already on the stored Node.
+                let data_now = node_data_id(&node);
+                let data_unchanged = data_now == data_before || data_now == node_data_id(&updated);
+
+                if data_unchanged && node_state(&node) == node_state(&snapshot) {
+                    *node = updated;
+                    return Ok(true);
+                }
+            }
+
+            Err(error::RuntimeError::new_err(format!(
+                "The Node of guild {} kept changing while updating it",
+                guild_id
+            )))
         })
     }
 
@@ -716,8 +3220,9 @@ impl Lavalink {
     fn get_guild_gateway_connection_info(
         &self,
         _py: Python<'_>,
-        guild_id: u64,
+        guild_id: GuildId,
     ) -> Option<ConnectionInfo> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         let connections = lava_client.discord_gateway_connections();
@@ -728,18 +3233,22 @@ impl Lavalink {
 
     /// Waits until the ConnectionInfo is complete and returns it.
     ///
+    /// Raises `NoConnectionInfo` if no voice information was received at all, and `TimeoutError`
+    /// if it was received, but it never got complete.
+    ///
     /// Positional Arguments:
     /// - `guild_id` : `Unsigned 64 bit integer`
     /// - `event_count` : `Unsigned 128 bit integer` defaults to 10
     ///
-    /// Returns: `Future<ConnectionInfo>`
+    /// Returns: `Future<Result<ConnectionInfo, [builtins.TimeoutError, lavasnek_rs.NoConnectionInfo]>>`
     #[pyo3(text_signature = "($self, guild_id, /, event_count=10)")]
     fn wait_for_full_connection_info_insert<'a>(
         &self,
         py: Python<'a>,
-        guild_id: u64,
+        guild_id: GuildId,
         event_count: Option<usize>,
     ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
@@ -749,7 +3258,7 @@ impl Lavalink {
                 event_count,
             )
             .await
-            .map_err(|e| error::TimeoutError::new_err(e.to_string()))?;
+            .map_err(|e| connection_info_error(&lava_client, guild_id, e.to_string()))?;
 
             Ok(ConnectionInfo {
                 inner: connection_info,
@@ -757,6 +3266,45 @@ impl Lavalink {
         })
     }
 
+    /// Waits until the next track of the guild starts playing, and returns the same data as the
+    /// `track_start` event.
+    ///
+    /// Call this before the track can start, like right before `queue()`, so the event is not
+    /// missed.
+    ///
+    /// Positional Arguments:
+    /// - `guild_id` : `Unsigned 64 bit integer`
+    /// - `timeout_secs` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `Future<Result<TrackStart, [builtins.TimeoutError]>>`
+    #[pyo3(text_signature = "($self, guild_id, timeout_secs, /)")]
+    fn wait_for_track_start<'a>(
+        &self,
+        py: Python<'a>,
+        guild_id: GuildId,
+        timeout_secs: u64,
+    ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+
+        {
+            let mut waiters = self.data.track_start_waiters.lock();
+            let guild_waiters = waiters.entry(guild_id).or_default();
+            // Drop the waiters that already timed out.
+            guild_waiters.retain(|waiter| !waiter.is_closed());
+            guild_waiters.push(sender);
+        }
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            match timeout(Duration::from_secs(timeout_secs), receiver).await {
+                Ok(Ok(event)) => Ok(event),
+                _ => Err(error::TimeoutError::new_err(
+                    "No track started before the timeout.",
+                )),
+            }
+        })
+    }
+
     /// Waits until the ConnectionInfo is removed.
     ///
     /// Positional Arguments:
@@ -768,10 +3316,11 @@ impl Lavalink {
     fn wait_for_connection_info_remove<'a>(
         &self,
         py: Python<'a>,
-        guild_id: u64,
+        guild_id: GuildId,
         event_count: Option<usize>,
         //) -> LavalinkResult<()> {
     ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
@@ -800,10 +3349,11 @@ impl Lavalink {
     fn raw_handle_event_voice_server_update<'a>(
         &self,
         py: Python<'a>,
-        guild_id: u64,
+        guild_id: GuildId,
         endpoint: String,
         token: String,
     ) -> PyResult<&'a PyAny> {
+        let guild_id = guild_id.0;
         let lava_client = self.lava.clone();
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
@@ -833,11 +3383,14 @@ impl Lavalink {
     fn raw_handle_event_voice_state_update(
         &self,
         _py: Python,
-        guild_id: u64,
-        user_id: u64,
+        guild_id: GuildId,
+        user_id: UserId,
         session_id: String,
-        channel_id: Option<u64>,
+        channel_id: Option<ChannelId>,
     ) {
+        let guild_id = guild_id.0;
+        let user_id = user_id.0;
+        let channel_id = channel_id.map(|id| id.0);
         let lava_client = self.lava.clone();
 
         lavalink_rs::voice::raw_handle_event_voice_state_update(
@@ -850,6 +3403,380 @@ impl Lavalink {
     }
 }
 
+/// How many times `Lavalink.update_node()` calls the callback again when the Node changed while it
+/// was running.
+const UPDATE_NODE_ATTEMPTS: usize = 10;
+
+/// The parts of a Node that the queue loop, the events and the `Lavalink` methods change, to tell
+/// if it changed since it was copied.
+fn node_state(node: &LavaNode) -> impl PartialEq {
+    let track_state = |track_queue: &LavaTrackQueue| {
+        (
+            track_queue.track.track.clone(),
+            track_queue.start_time,
+            track_queue.end_time,
+            track_queue.requester.map(|id| id.0),
+        )
+    };
+
+    (
+        node.volume,
+        node.is_paused,
+        node.is_on_loops,
+        node.now_playing.as_ref().map(track_state),
+        node.queue.iter().map(track_state).collect::<Vec<_>>(),
+    )
+}
+
+/// Joins the voice channel, waiting up to `voice_timeout` for the gateway to receive the full
+/// connection information.
+///
+/// lavalink-rs gives up on its own after 5 seconds, so if the timeout is longer than that, the
+/// gateway connections are polled until the information is complete or the timeout passes.
+async fn join_with_timeout(
+    lava_client: &LavalinkClient,
+    guild_id: u64,
+    channel_id: u64,
+    voice_timeout: Duration,
+) -> PyResult<LavaConnectionInfo> {
+    let deadline = Instant::now() + voice_timeout;
+
+    let join_error = match timeout(voice_timeout, lava_client.join(guild_id, channel_id)).await {
+        Ok(Ok(connection_info)) => return Ok(connection_info),
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => "Timed out joining the voice channel".to_string(),
+    };
+
+    while Instant::now() < deadline {
+        if let Some(connection_info) = lava_client
+            .discord_gateway_connections()
+            .get(&guild_id.into())
+        {
+            if connection_info.endpoint.is_some()
+                && connection_info.token.is_some()
+                && connection_info.session_id.is_some()
+            {
+                return Ok(connection_info.clone());
+            }
+        }
+
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    Err(connection_info_error(lava_client, guild_id, join_error))
+}
+
+/// Raises `NoConnectionInfo` if discord never sent any voice information for the guild, which
+/// usually means that the bot is missing permissions, and `TimeoutError` if the information is
+/// there, but incomplete.
+fn connection_info_error(lava_client: &LavalinkClient, guild_id: u64, message: String) -> PyErr {
+    let has_connection_info = lava_client
+        .discord_gateway_connections()
+        .get(&guild_id.into())
+        .is_some();
+
+    if has_connection_info {
+        error::TimeoutError::new_err(message)
+    } else {
+        error::NoConnectionInfo::new_err(
+            "No voice connection information was received, check that the bot has permission to join the voice channel",
+        )
+    }
+}
+
+/// Joins the voice channel again and creates a new session, restoring the Node that the guild had
+/// before, so the queue is not lost.
+async fn rejoin_keeping_node(
+    lava_client: &LavalinkClient,
+    guild_id: u64,
+    channel_id: u64,
+    voice_timeout: Duration,
+) -> PyResult<LavaConnectionInfo> {
+    let connection_info =
+        join_with_timeout(lava_client, guild_id, channel_id, voice_timeout).await?;
+
+    // Taken after joining, so the changes made while waiting for the gateway are kept.
+    let node = lava_client
+        .nodes()
+        .await
+        .get(&guild_id)
+        .map(|node| node.clone());
+
+    lava_client
+        .create_session(&connection_info)
+        .await
+        .map_err(|e| error::TypeError::new_err(format!("Missing field '{}'", e)))?;
+
+    if let Some(node) = node {
+        restore_node(lava_client, guild_id, node).await;
+    }
+
+    Ok(connection_info)
+}
+
+/// Puts back the Node a guild had before `create_session()` replaced it with a new one.
+///
+/// The live Node is merged into it rather than overwritten, so the tracks queued on the new Node
+/// while the session was being created, and the track the queue loop started on it, are kept. If
+/// the guild no longer has a Node, the previous one is inserted as is.
+async fn restore_node(lava_client: &LavalinkClient, guild_id: u64, previous: LavaNode) {
+    let nodes = lava_client.nodes().await;
+
+    let mut node = match nodes.get_mut(&guild_id) {
+        Some(node) => node,
+        None => {
+            nodes.insert(guild_id, previous);
+            return;
+        }
+    };
+
+    let mut queue = previous.queue.clone();
+    queue.extend(node.queue.drain(..));
+
+    let now_playing = node
+        .now_playing
+        .take()
+        .or_else(|| previous.now_playing.clone());
+
+    *node = LavaNode {
+        queue,
+        now_playing,
+        ..previous
+    };
+}
+
+/// Destroys the session, removes the guild Node and loop, and leaves the voice channel, in that
+/// order.
+async fn teardown_guild(
+    lava_client: &LavalinkClient,
+    guild_id: u64,
+    voice_timeout: Duration,
+) -> PyResult<()> {
+    lava_client
+        .destroy(guild_id)
+        .await
+        .map_err(error::player_error)?;
+
+    lava_client.nodes().await.remove(&guild_id);
+    lava_client.loops().await.remove(&guild_id);
+
+    leave_with_timeout(lava_client, guild_id, voice_timeout).await
+}
+
+/// Leaves the voice channel, waiting up to `voice_timeout` for the gateway to remove the connection
+/// information.
+///
+/// lavalink-rs gives up on its own after 5 seconds, so if the timeout is longer than that, the
+/// gateway connections are polled until the information is removed or the timeout passes.
+async fn leave_with_timeout(
+    lava_client: &LavalinkClient,
+    guild_id: u64,
+    voice_timeout: Duration,
+) -> PyResult<()> {
+    let deadline = Instant::now() + voice_timeout;
+
+    let leave_error = match timeout(voice_timeout, lava_client.leave(guild_id)).await {
+        Ok(Ok(_)) => return Ok(()),
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => "Timed out leaving the voice channel".to_string(),
+    };
+
+    while Instant::now() < deadline {
+        let is_connected = lava_client
+            .discord_gateway_connections()
+            .get(&guild_id.into())
+            .is_some();
+
+        if !is_connected {
+            return Ok(());
+        }
+
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    Err(error::TimeoutError::new_err(leave_error))
+}
+
+/// Awaits a REST request, raising `TimeoutError` if it takes longer than the REST timeout configured
+/// on the builder, and `NetworkError` if the request fails.
+async fn rest_with_timeout<T, E: std::fmt::Display>(
+    rest_timeout: Option<Duration>,
+    request: impl Future<Output = Result<T, E>>,
+) -> PyResult<T> {
+    let result = match rest_timeout {
+        Some(rest_timeout) => timeout(rest_timeout, request).await.map_err(|_| {
+            error::TimeoutError::new_err("Timed out waiting for the Lavalink server")
+        })?,
+        None => request.await,
+    };
+
+    result.map_err(|e| error::NetworkError::new_err(e.to_string()))
+}
+
+/// Like `rest_with_timeout()`, but retries the request when it fails, waiting longer on every retry,
+/// as configured with `LavalinkBuilder.set_rest_retries()`.
+///
+/// Only use it for requests that are safe to repeat, like loading tracks.
+async fn rest_with_retries<T, E: std::fmt::Display, F: Future<Output = Result<T, E>>>(
+    rest_timeout: Option<Duration>,
+    rest_retries: RestRetries,
+    request: impl Fn() -> F,
+) -> PyResult<T> {
+    let mut retry = 0;
+
+    loop {
+        match rest_with_timeout(rest_timeout, request()).await {
+            Err(e) if retry < rest_retries.count => {
+                let backoff = rest_retries
+                    .backoff
+                    .saturating_mul(2u32.saturating_pow(retry));
+                debug!("REST request failed, retrying in {:?}: {}", backoff, e);
+
+                sleep(backoff).await;
+                retry += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Searches the query on youtube, using the search cache if it's enabled.
+async fn search_youtube(
+    lava_client: &LavalinkClient,
+    data: &LavalinkData,
+    query: String,
+) -> PyResult<LavaTracks> {
+    let cache = data
+        .config
+        .search_cache
+        .map(|config| (config, SearchCache::key("ytsearch", &query)));
+
+    if let Some((config, key)) = &cache {
+        if let Some(tracks) = data.search_cache.get(config, key) {
+            return Ok(tracks);
+        }
+    }
+
+    let tracks = rest_with_retries(data.config.rest_timeout, data.config.rest_retries, || {
+        lava_client.search_tracks(query.clone())
+    })
+    .await?;
+
+    if let Some((config, key)) = cache {
+        if !tracks.tracks.is_empty() {
+            data.search_cache.insert(&config, key, tracks.clone());
+        }
+    }
+
+    Ok(tracks)
+}
+
+/// Checks that an equalizer band is in the range Lavalink accepts.
+fn validate_band(band: u8, gain: f64) -> PyResult<()> {
+    if band > 14 {
+        return Err(error::ValueError::new_err(format!(
+            "Band {} doesn't exist, bands go from 0 to 14",
+            band
+        )));
+    }
+
+    if !(-0.25..=1.0).contains(&gain) {
+        return Err(error::ValueError::new_err(format!(
+            "Band {} has a gain of {}, it must be between -0.25 and 1.0",
+            band, gain
+        )));
+    }
+
+    Ok(())
+}
+
+/// Builds the `LoadFailedError` of a failed load, with the message and severity of the exception
+/// sent by Lavalink.
+///
+/// lavalink-rs doesn't keep the exception of the load result, so the identifier is loaded again
+/// through the REST API to get it. If that fails too, the error only has the identifier.
+async fn load_failed_error(
+    rest: &LavalinkRest,
+    rest_timeout: Option<Duration>,
+    identifier: &str,
+) -> PyErr {
+    let result = rest_with_timeout(rest_timeout, async {
+        rest.get("/loadtracks")
+            .query(&[("identifier", identifier)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<serde_json::Value>()
+            .await
+    })
+    .await;
+
+    let exception = result.ok().and_then(|result| {
+        let exception = &result["exception"];
+
+        exception["message"].as_str().map(|message| {
+            format!(
+                "{} (severity: {})",
+                message,
+                exception["severity"].as_str().unwrap_or("UNKNOWN")
+            )
+        })
+    });
+
+    match exception {
+        Some(exception) => error::LoadFailedError::new_err(format!(
+            "Lavalink failed to load '{}': {}",
+            identifier, exception
+        )),
+        None => {
+            error::LoadFailedError::new_err(format!("Lavalink failed to load '{}'", identifier))
+        }
+    }
+}
+
+/// Raises `LoadFailedError` if the source comes from the LavaSrc plugin and the Lavalink server
+/// doesn't have it, so a missing plugin doesn't look like a search without results.
+async fn ensure_plugin_source(
+    rest: &LavalinkRest,
+    rest_timeout: Option<Duration>,
+    source: &Source,
+) -> PyResult<()> {
+    let source_manager = match source.plugin_source_manager {
+        Some(source_manager) => source_manager,
+        None => return Ok(()),
+    };
+
+    let response = rest_with_timeout(rest_timeout, rest.get("/v3/info").send()).await?;
+
+    // Servers without `/v3/info` are too old for the LavaSrc plugin.
+    let has_source = if response.status() == reqwest::StatusCode::NOT_FOUND {
+        false
+    } else {
+        let info = rest_with_timeout(rest_timeout, async {
+            response
+                .error_for_status()?
+                .json::<serde_json::Value>()
+                .await
+        })
+        .await?;
+
+        info["sourceManagers"].as_array().map_or(false, |managers| {
+            managers
+                .iter()
+                .any(|manager| manager.as_str() == Some(source_manager))
+        })
+    };
+
+    if has_source {
+        Ok(())
+    } else {
+        Err(error::LoadFailedError::new_err(format!(
+            "The Lavalink server doesn't have the {} source, install the LavaSrc plugin to use it",
+            source.name
+        )))
+    }
+}
+
 /// Test function, do not use.
 #[pyfunction]
 #[pyo3(text_signature = "(seconds, /)")]
@@ -908,6 +3835,7 @@ fn lavasnek_rs(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<Info>()?;
     m.add_class::<PlaylistInfo>()?;
     m.add_class::<Node>()?;
+    m.add_class::<QueuePage>()?;
     m.add_class::<Band>()?;
 
     // event models
@@ -919,10 +3847,16 @@ fn lavasnek_rs(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<TrackStuck>()?;
     m.add_class::<WebSocketClosed>()?;
     m.add_class::<PlayerDestroyed>()?;
+    m.add_class::<IdleTimeout>()?;
+    m.add_class::<AutoplayTrack>()?;
 
     // exceptions
     m.add("NoSessionPresent", py.get_type::<error::NoSessionPresent>())?;
     m.add("NetworkError", py.get_type::<error::NetworkError>())?;
+    m.add("PlayerError", py.get_type::<error::PlayerError>())?;
+    m.add("LoadFailedError", py.get_type::<error::LoadFailedError>())?;
+    m.add("NoConnectionInfo", py.get_type::<error::NoConnectionInfo>())?;
+    m.add("QueueError", py.get_type::<error::QueueError>())?;
 
     Ok(())
 }
@@ -1,3 +1,5 @@
+use crate::error;
+
 use lavalink_rs::model::{
     Band as LavaBand, ConnectionInfo as LavaConnectionInfo, Info as LavaInfo, Node as LavaNode,
     PlayerDestroyed as LavaPlayerDestroyed, PlayerUpdate as LavaPlayerUpdate,
@@ -7,7 +9,14 @@ use lavalink_rs::model::{
     Tracks as LavaTracks, WebSocketClosed as LavaWebSocketClosed,
 };
 use lavalink_rs::typemap_rev::TypeMapKey;
-use pyo3::{prelude::*, types::PyDict};
+use pyo3::{
+    class::{basic::CompareOp, PyIterProtocol, PyMappingProtocol, PyObjectProtocol},
+    prelude::*,
+    types::{PyDict, PySlice},
+};
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 struct NodeData;
 
@@ -15,6 +24,131 @@ impl TypeMapKey for NodeData {
     type Value = PyObject;
 }
 
+/// Returns the data stored on the Node, storing an empty dict first if no data was set.
+pub fn node_data(py: Python<'_>, node: &LavaNode) -> PyObject {
+    let data_lock = node.data.clone();
+    let dict = PyDict::new(py).into_py(py);
+
+    let contains_key = data_lock.read().contains_key::<NodeData>();
+
+    if !contains_key {
+        data_lock.write().insert::<NodeData>(dict)
+    }
+
+    let data_read = data_lock.read();
+    data_read.get::<NodeData>().unwrap().clone()
+}
+
+pub fn set_node_data(node: &LavaNode, data: PyObject) {
+    node.data.write().insert::<NodeData>(data);
+}
+
+/// Identifies the data object stored on the Node, to tell if it was replaced without needing the
+/// GIL.
+pub fn node_data_id(node: &LavaNode) -> Option<usize> {
+    node.data
+        .read()
+        .get::<NodeData>()
+        .map(|data| data.as_ptr() as usize)
+}
+
+fn optional_repr<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "None".to_string(), |v| v.to_string())
+}
+
+fn info_repr(info: &LavaInfo) -> String {
+    format!(
+        "Info(title={:?}, author={:?}, uri={:?}, length={})",
+        info.title, info.author, info.uri, info.length
+    )
+}
+
+/// Lavalink band gains are multipliers where -0.25 mutes the band and 0.25 doubles it, so the
+/// amplitude is `1 + 4 * gain`.
+fn db_to_gain(db: f64) -> f64 {
+    let amplitude = 10f64.powf(db / 20.0);
+
+    ((amplitude - 1.0) / 4.0).clamp(-0.25, 1.0)
+}
+
+fn track_repr(track: &LavaTrack) -> String {
+    match &track.info {
+        Some(info) => format!("Track(info={})", info_repr(info)),
+        None => format!("Track(track={:?}, info=None)", track.track),
+    }
+}
+
+/// Raises `ValueError` if the track would end before it starts.
+pub fn validate_track_times(start_time: u64, end_time: Option<u64>) -> PyResult<()> {
+    match end_time {
+        Some(end_time) if end_time <= start_time => Err(error::ValueError::new_err(format!(
+            "end_time ({}) must be greater than start_time ({})",
+            end_time, start_time
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Raises `ValueError` if the volume is above 1000, the max Lavalink accepts.
+pub fn validate_volume(name: &str, volume: u16) -> PyResult<()> {
+    if volume > 1000 {
+        return Err(error::ValueError::new_err(format!(
+            "{} must be between 0 and 1000, got {}",
+            name, volume
+        )));
+    }
+
+    Ok(())
+}
+
+/// Converts a queued track into a dict with only JSON compatible values, so it can be stored.
+///
+/// The track info is not included, as it can be obtained again with `Lavalink.decode_track()`.
+pub fn track_queue_to_dict(py: Python<'_>, track_queue: &LavaTrackQueue) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+
+    dict.set_item("track", &track_queue.track.track)?;
+    dict.set_item("start_time", track_queue.start_time)?;
+    dict.set_item("end_time", track_queue.end_time)?;
+    dict.set_item("requester", track_queue.requester.map(|u| u.0))?;
+
+    Ok(dict.into_py(py))
+}
+
+/// Builds a queued track from a dict made by `track_queue_to_dict()`.
+///
+/// Only the `track` key is required.
+pub fn track_queue_from_dict(dict: &PyDict) -> PyResult<LavaTrackQueue> {
+    let track = dict
+        .get_item("track")
+        .ok_or_else(|| error::TypeError::new_err("Missing field 'track'"))?
+        .extract::<String>()?;
+
+    let start_time = match dict.get_item("start_time") {
+        Some(start_time) => start_time.extract::<Option<u64>>()?.unwrap_or_default(),
+        None => 0,
+    };
+
+    let end_time = match dict.get_item("end_time") {
+        Some(end_time) => end_time.extract::<Option<u64>>()?,
+        None => None,
+    };
+
+    let requester = match dict.get_item("requester") {
+        Some(requester) => requester.extract::<Option<u64>>()?,
+        None => None,
+    };
+
+    validate_track_times(start_time, end_time)?;
+
+    Ok(LavaTrackQueue {
+        track: LavaTrack { track, info: None },
+        start_time,
+        end_time,
+        requester: requester.map(|u| u.into()),
+    })
+}
+
 /// If you use a 3rd party method of joining a voice channel, you can get the values
 /// required for this from the `VOICE_STATE_UPDATE` and
 /// `VOICE_SERVER_UPDATE` events, and use raw_handle_event_voice_state_update() +
@@ -98,13 +232,180 @@ impl Tracks {
             .map(|i| Track { inner: i.clone() })
             .collect()
     }
+
+    /// If the load type is `TRACK_LOADED`, a single track was loaded from a URL.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_track_loaded(&self) -> bool {
+        self.inner.load_type == "TRACK_LOADED"
+    }
+
+    /// If the load type is `PLAYLIST_LOADED`, a playlist was loaded, and `playlist_info` is set.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_playlist(&self) -> bool {
+        self.inner.load_type == "PLAYLIST_LOADED"
+    }
+
+    /// If the load type is `SEARCH_RESULT`, the tracks are the results of a search.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_search_result(&self) -> bool {
+        self.inner.load_type == "SEARCH_RESULT"
+    }
+
+    /// If the load type is `NO_MATCHES`, nothing was found.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_no_matches(&self) -> bool {
+        self.inner.load_type == "NO_MATCHES"
+    }
+
+    /// If the load type is `LOAD_FAILED`, Lavalink failed to load the query.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_load_failed(&self) -> bool {
+        self.inner.load_type == "LOAD_FAILED"
+    }
+
+    /// Returns up to `limit` tracks starting at `offset`, without copying the rest of the tracks,
+    /// to show big playlists in pages.
+    ///
+    /// Returns an empty list if `offset` is past the last track.
+    ///
+    /// ```py
+    /// tracks = await lavalink.get_tracks(playlist_url)
+    /// page = tracks.page(page_number * 10, 10)
+    /// ```
+    ///
+    /// Positional Arguments:
+    /// - `offset` : `Unsigned 64 bit integer`
+    /// - `limit` : `Unsigned 64 bit integer`
+    ///
+    /// Returns: `List<Track>`
+    #[pyo3(text_signature = "($self, offset, limit, /)")]
+    fn page(&self, offset: usize, limit: usize) -> Vec<Track> {
+        self.inner
+            .tracks
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|i| Track { inner: i.clone() })
+            .collect()
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for Tracks {
+    fn __repr__(&self) -> String {
+        format!(
+            "Tracks(load_type={:?}, playlist_name={}, tracks={})",
+            self.inner.load_type,
+            optional_repr(
+                self.inner
+                    .playlist_info
+                    .as_ref()
+                    .and_then(|pi| pi.name.as_ref())
+                    .map(|name| format!("{:?}", name))
+            ),
+            self.inner.tracks.len(),
+        )
+    }
+}
+
+#[pyproto]
+impl PyMappingProtocol for Tracks {
+    fn __len__(&self) -> usize {
+        self.inner.tracks.len()
+    }
+
+    /// Returns a single `Track` for an integer index, or a `List<Track>` for a slice, without
+    /// copying the rest of the tracks.
+    fn __getitem__(&self, key: &PyAny) -> PyResult<PyObject> {
+        let py = key.py();
+        let tracks = &self.inner.tracks;
+
+        if let Ok(slice) = key.downcast::<PySlice>() {
+            let indices = slice.indices(tracks.len() as _)?;
+
+            let selected = (0..indices.slicelength)
+                .map(|i| indices.start + i * indices.step)
+                .map(|i| Track {
+                    inner: tracks[i as usize].clone(),
+                })
+                .collect::<Vec<_>>();
+
+            return Ok(selected.into_py(py));
+        }
+
+        let index = key.extract::<isize>()?;
+        let normalized = if index < 0 {
+            index + tracks.len() as isize
+        } else {
+            index
+        };
+
+        if normalized < 0 || normalized as usize >= tracks.len() {
+            return Err(error::IndexError::new_err("Tracks index out of range"));
+        }
+
+        Ok(Track {
+            inner: tracks[normalized as usize].clone(),
+        }
+        .into_py(py))
+    }
+}
+
+#[pyproto]
+impl PyIterProtocol for Tracks {
+    fn __iter__(slf: PyRef<Self>) -> TracksIter {
+        TracksIter {
+            tracks: slf.into(),
+            index: 0,
+        }
+    }
+}
+
+/// Iterator over the tracks of `Tracks`, which copies each `Track` only when it's reached.
+#[pyclass]
+pub struct TracksIter {
+    tracks: Py<Tracks>,
+    index: usize,
+}
+
+#[pyproto]
+impl PyIterProtocol for TracksIter {
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<Self>) -> Option<Track> {
+        let track = slf
+            .tracks
+            .borrow(slf.py())
+            .inner
+            .tracks
+            .get(slf.index)
+            .cloned();
+
+        slf.index += 1;
+
+        track.map(|inner| Track { inner })
+    }
 }
 
 /// __new__()
 ///
 /// Positional Arguments:
 /// - `track` : `str`
-/// - `info` : `Option<Info>`
+///
+/// Arguments:
+/// - `info` : `Option<Info>` -- defaults to None
 ///
 /// Returns: `Self`
 #[pyclass]
@@ -116,6 +417,7 @@ pub struct Track {
 #[pymethods]
 impl Track {
     #[new]
+    #[args(info = "None")]
     fn new(track: String, info: Option<Info>) -> Self {
         let t = LavaTrack {
             track,
@@ -152,6 +454,47 @@ impl Track {
     }
 }
 
+#[pyproto]
+impl PyObjectProtocol for Track {
+    fn __repr__(&self) -> String {
+        track_repr(&self.inner)
+    }
+
+    /// Tracks are equal if their playable base 64 track is the same, the info is not compared.
+    fn __richcmp__(&self, other: &PyAny, op: CompareOp) -> PyObject {
+        let py = other.py();
+
+        let other = match other.extract::<PyRef<Track>>() {
+            Ok(other) => other,
+            Err(_) => return py.NotImplemented(),
+        };
+
+        match op {
+            CompareOp::Eq => (self.inner.track == other.inner.track).into_py(py),
+            CompareOp::Ne => (self.inner.track != other.inner.track).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+
+    fn __hash__(&self) -> isize {
+        let mut hasher = DefaultHasher::new();
+        self.inner.track.hash(&mut hasher);
+
+        hasher.finish() as isize
+    }
+}
+
+/// __new__()
+///
+/// Positional Arguments:
+/// - `track` : `Track`
+///
+/// Arguments:
+/// - `start_time` : `Unsigned 64 bit integer` -- defaults to 0
+/// - `end_time` : `Option<Unsigned 64 bit integer>` -- defaults to None
+/// - `requester` : `Option<Unsigned 64 bit integer>` -- defaults to None
+///
+/// Returns: `Result<Self, [builtins.ValueError]>`
 #[pyclass]
 #[derive(Clone, Debug)]
 pub struct TrackQueue {
@@ -160,6 +503,26 @@ pub struct TrackQueue {
 
 #[pymethods]
 impl TrackQueue {
+    #[new]
+    #[args(start_time = "0", end_time = "None", requester = "None")]
+    fn new(
+        track: Track,
+        start_time: u64,
+        end_time: Option<u64>,
+        requester: Option<u64>,
+    ) -> PyResult<Self> {
+        validate_track_times(start_time, end_time)?;
+
+        Ok(Self {
+            inner: LavaTrackQueue {
+                track: track.inner,
+                start_time,
+                end_time,
+                requester: requester.map(|u| u.into()),
+            },
+        })
+    }
+
     #[getter]
     /// The playable track.
     ///
@@ -193,6 +556,58 @@ impl TrackQueue {
     fn requester(&self) -> Option<u64> {
         self.inner.requester.map(|u| u.0)
     }
+
+    #[setter]
+    fn set_start_time(&mut self, start_time: u64) -> PyResult<()> {
+        validate_track_times(start_time, self.inner.end_time)?;
+        self.inner.start_time = start_time;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_end_time(&mut self, end_time: Option<u64>) -> PyResult<()> {
+        validate_track_times(self.inner.start_time, end_time)?;
+        self.inner.end_time = end_time;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_requester(&mut self, requester: Option<u64>) {
+        self.inner.requester = requester.map(|u| u.into());
+    }
+
+    /// Calculates how many milliseconds are left for the track to finish, given the current
+    /// position of the player, which can be obtained from `PlayerUpdate.state_position`.
+    ///
+    /// If `end_time` is set, the remaining time is measured against it instead of the full length of
+    /// the track.
+    ///
+    /// Returns `None` if the track doesn't have info, as the length is unknown.
+    ///
+    /// Positional Arguments:
+    /// - `position` : `Unsigned 64 bit integer` -- milliseconds
+    ///
+    /// Returns: `Option<Unsigned 64 bit integer>`
+    #[pyo3(text_signature = "($self, position, /)")]
+    fn remaining_ms(&self, position: u64) -> Option<u64> {
+        let length = self.inner.track.info.as_ref()?.length;
+        let end = self.inner.end_time.unwrap_or(length);
+
+        Some(end.saturating_sub(position))
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for TrackQueue {
+    fn __repr__(&self) -> String {
+        format!(
+            "TrackQueue(track={}, start_time={}, end_time={}, requester={})",
+            track_repr(&self.inner.track),
+            self.inner.start_time,
+            optional_repr(self.inner.end_time),
+            optional_repr(self.inner.requester.map(|u| u.0)),
+        )
+    }
 }
 
 #[pyclass]
@@ -322,6 +737,37 @@ impl Info {
         self.inner.uri.clone()
     }
 
+    #[getter]
+    /// The length of the track in seconds.
+    ///
+    /// Contains `Unsigned 64 bit integer`
+    fn get_length_secs(&self) -> u64 {
+        self.inner.length / 1000
+    }
+
+    /// Formats the length of the track as `H:MM:SS`, or `M:SS` if it's shorter than an hour.
+    ///
+    /// Streams don't have a known length, so they will return `"LIVE"` instead.
+    ///
+    /// Returns: `String`
+    #[pyo3(text_signature = "($self, /)")]
+    fn formatted_length(&self) -> String {
+        if self.inner.is_stream {
+            return "LIVE".to_string();
+        }
+
+        let total_secs = self.inner.length / 1000;
+        let hours = total_secs / 3600;
+        let minutes = (total_secs % 3600) / 60;
+        let seconds = total_secs % 60;
+
+        if hours > 0 {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{}:{:02}", minutes, seconds)
+        }
+    }
+
     #[setter]
     fn set_length(&mut self, length: u64) {
         self.inner.length = length
@@ -363,6 +809,13 @@ impl Info {
     }
 }
 
+#[pyproto]
+impl PyObjectProtocol for Info {
+    fn __repr__(&self) -> String {
+        info_repr(&self.inner)
+    }
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct Node {
@@ -420,8 +873,10 @@ impl Node {
     }
 
     #[setter]
-    fn set_volume(&mut self, volume: u16) {
-        self.inner.volume = volume
+    fn set_volume(&mut self, volume: u16) -> PyResult<()> {
+        validate_volume("volume", volume)?;
+        self.inner.volume = volume;
+        Ok(())
     }
 
     #[setter]
@@ -444,6 +899,65 @@ impl Node {
         self.inner.queue = queue.iter().map(|i| i.inner.clone()).collect();
     }
 
+    /// Returns the number of tracks in the queue, without copying them like `queue` does.
+    ///
+    /// Returns `Unsigned 64 bit integer`
+    #[pyo3(text_signature = "($self, /)")]
+    fn queue_len(&self) -> usize {
+        self.inner.queue.len()
+    }
+
+    /// Returns the number of tracks loaded on the Node, the current track included.
+    ///
+    /// With the queue loop, the current track is both `now_playing` and the first track of
+    /// `queue`, so it's counted once. Tracks started with `PlayBuilder.start()` are not in the
+    /// queue, so they're counted on top of it.
+    ///
+    /// Returns `Unsigned 64 bit integer`
+    #[pyo3(text_signature = "($self, /)")]
+    fn total_count(&self) -> usize {
+        let queue = &self.inner.queue;
+        let first = queue.first().map(|i| &i.track.track);
+
+        match &self.inner.now_playing {
+            Some(now_playing) if first != Some(&now_playing.track.track) => queue.len() + 1,
+            _ => queue.len(),
+        }
+    }
+
+    /// Returns the track of the queue at the index, without copying the rest of the queue like
+    /// `queue` does, or `None` if the index is out of range.
+    ///
+    /// Positional Arguments:
+    /// - `index` : `Unsigned 64 bit integer`
+    ///
+    /// Returns `Option<TrackQueue>`
+    #[pyo3(text_signature = "($self, index, /)")]
+    fn queue_at(&self, index: usize) -> Option<TrackQueue> {
+        self.inner
+            .queue
+            .get(index)
+            .map(|i| TrackQueue { inner: i.clone() })
+    }
+
+    /// Returns the state of the player as a string:
+    ///
+    /// - `"stopped"` if nothing is playing.
+    /// - `"paused"` if a track is playing, but it's paused.
+    /// - `"playing"` if a track is playing.
+    ///
+    /// Returns `String`
+    #[pyo3(text_signature = "($self, /)")]
+    fn player_state(&self) -> &'static str {
+        if self.inner.now_playing.is_none() {
+            "stopped"
+        } else if self.inner.is_paused {
+            "paused"
+        } else {
+            "playing"
+        }
+    }
+
     /// Use this to get the currently stored data on the Node.
     ///
     /// `T` is whatever type you give to `set_data`'s data parameter, but if you call this method before it,
@@ -452,17 +966,59 @@ impl Node {
     /// Returns `T`
     #[pyo3(text_signature = "($self, /)")]
     fn get_data(&self, py: Python<'_>) -> Py<PyAny> {
+        node_data(py, &self.inner)
+    }
+
+    /// Use this to get the currently stored data on the Node, initializing it with the return value
+    /// of `factory` if no data has been set yet.
+    ///
+    /// If two calls race to initialize the data, the factory may be called by both, but only the
+    /// first value is stored, and it's what both of them return.
+    ///
+    /// Positional Arguments:
+    /// - `factory` : `Callable[[], T]`
+    ///
+    /// Returns `T`
+    #[pyo3(text_signature = "($self, factory, /)")]
+    fn get_or_init_data(&self, py: Python<'_>, factory: PyObject) -> PyResult<Py<PyAny>> {
         let data_lock = self.inner.data.clone();
-        let dict = PyDict::new(py).into_py(py);
 
-        let contains_key = data_lock.read().contains_key::<NodeData>();
+        if let Some(data) = data_lock.read().get::<NodeData>() {
+            return Ok(data.clone());
+        }
+
+        let data = factory.call0(py)?;
+
+        let mut data_write = data_lock.write();
 
-        if !contains_key {
-            data_lock.write().insert::<NodeData>(dict)
+        if !data_write.contains_key::<NodeData>() {
+            data_write.insert::<NodeData>(data);
         }
 
-        let data_read = data_lock.read();
-        data_read.get::<NodeData>().unwrap().clone()
+        Ok(data_write.get::<NodeData>().unwrap().clone())
+    }
+
+    /// Use this to set a single key of the stored data of the Node, without replacing the rest of
+    /// it, so different parts of a bot can share it.
+    ///
+    /// If no data has been set yet, it will default to a Dict.
+    ///
+    /// Raises `TypeError` if the stored data is not a Dict.
+    ///
+    /// Positional Arguments:
+    /// - `key` : `Any`
+    /// - `value` : `Any`
+    ///
+    /// Returns `Result<None, builtins.TypeError>`
+    #[pyo3(text_signature = "($self, key, value, /)")]
+    fn update_data(&self, py: Python<'_>, key: PyObject, value: PyObject) -> PyResult<()> {
+        let data = self.get_data(py);
+        let dict = data
+            .as_ref(py)
+            .downcast::<PyDict>()
+            .map_err(|_| error::TypeError::new_err("The stored Node data is not a dict"))?;
+
+        dict.set_item(key, value)
     }
 
     /// Use this to set the tored data of the Node.
@@ -470,9 +1026,91 @@ impl Node {
     /// Returns `None`
     #[pyo3(text_signature = "($self, data, /)")]
     fn set_data(&self, _py: Python, data: PyObject) {
-        let data_lock = self.inner.data.clone();
+        set_node_data(&self.inner, data);
+    }
+}
 
-        data_lock.write().insert::<NodeData>(data);
+#[pyproto]
+impl PyObjectProtocol for Node {
+    fn __repr__(&self) -> String {
+        format!(
+            "Node(guild={}, now_playing={}, queue_len={}, volume={}, is_paused={})",
+            self.inner.guild.0,
+            optional_repr(
+                self.inner
+                    .now_playing
+                    .as_ref()
+                    .and_then(|np| np.track.info.as_ref())
+                    .map(|info| format!("{:?}", info.title))
+            ),
+            self.inner.queue.len(),
+            self.inner.volume,
+            if self.inner.is_paused {
+                "True"
+            } else {
+                "False"
+            },
+        )
+    }
+}
+
+/// A page of the queue of a guild, returned by `Lavalink.get_queue_page()`.
+#[pyclass]
+#[derive(Clone)]
+pub struct QueuePage {
+    pub tracks: Vec<LavaTrackQueue>,
+    pub page: usize,
+    pub total_tracks: usize,
+    pub total_pages: usize,
+}
+
+#[pymethods]
+impl QueuePage {
+    #[getter]
+    /// Contains `List<TrackQueue>`
+    fn tracks(&self) -> Vec<TrackQueue> {
+        self.tracks
+            .iter()
+            .map(|i| TrackQueue { inner: i.clone() })
+            .collect()
+    }
+
+    #[getter]
+    /// The index of the page, starting at 0. It can be lower than the requested page, as
+    /// out-of-range pages are clamped to the last page.
+    ///
+    /// Contains `Unsigned 64 bit integer`
+    fn page(&self) -> usize {
+        self.page
+    }
+
+    #[getter]
+    /// The number of tracks in the whole queue.
+    ///
+    /// Contains `Unsigned 64 bit integer`
+    fn total_tracks(&self) -> usize {
+        self.total_tracks
+    }
+
+    #[getter]
+    /// The number of pages of the queue, at least 1 even if the queue is empty.
+    ///
+    /// Contains `Unsigned 64 bit integer`
+    fn total_pages(&self) -> usize {
+        self.total_pages
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for QueuePage {
+    fn __repr__(&self) -> String {
+        format!(
+            "QueuePage(page={}, total_pages={}, total_tracks={}, tracks={})",
+            self.page,
+            self.total_pages,
+            self.total_tracks,
+            self.tracks.len(),
+        )
     }
 }
 
@@ -521,6 +1159,55 @@ impl Band {
     fn set_gain(&mut self, val: f64) {
         self.inner.gain = val
     }
+
+    /// Creates a band with the gain in decibels, instead of the multiplier Lavalink uses.
+    ///
+    /// The gain is clamped to what Lavalink accepts, which is muted at the lowest, and about
+    /// +14 dB at the highest.
+    ///
+    /// ```py
+    /// band = Band.from_db_gain(0, 6.0) # twice as loud
+    /// ```
+    ///
+    /// Positional Arguments:
+    /// - `band` : `Unsigned 8 bit integer` -- 0 to 14
+    /// - `db` : `64 bit float`
+    ///
+    /// Returns: `Band`
+    #[staticmethod]
+    #[pyo3(text_signature = "(band, db, /)")]
+    fn from_db_gain(band: u8, db: f64) -> Self {
+        Self::new(band, db_to_gain(db))
+    }
+
+    /// Creates the bands of a bass boost, raising the lowest bands by `amount` decibels, and the
+    /// next one by half of it, to pass to `Lavalink.equalize_dynamic()`.
+    ///
+    /// ```py
+    /// await lavalink.equalize_dynamic(guild_id, Band.bass_boost(6.0))
+    /// ```
+    ///
+    /// Positional Arguments:
+    /// - `amount` : `64 bit float` -- decibels
+    ///
+    /// Returns: `List<Band>`
+    #[staticmethod]
+    #[pyo3(text_signature = "(amount, /)")]
+    fn bass_boost(amount: f64) -> Vec<Self> {
+        vec![
+            Self::from_db_gain(0, amount),
+            Self::from_db_gain(1, amount),
+            Self::from_db_gain(2, amount),
+            Self::from_db_gain(3, amount / 2.0),
+        ]
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for Band {
+    fn __repr__(&self) -> String {
+        format!("Band(band={}, gain={})", self.inner.band, self.inner.gain)
+    }
 }
 
 #[pyclass]
@@ -620,6 +1307,7 @@ impl Stats {
 #[derive(Clone)]
 pub struct PlayerUpdate {
     pub inner: LavaPlayerUpdate,
+    pub ping_ms: i64,
 }
 
 #[pymethods]
@@ -647,12 +1335,23 @@ impl PlayerUpdate {
     fn state_time(&self) -> i64 {
         self.inner.state.time
     }
+
+    #[getter]
+    /// How long the update took to arrive, in milliseconds, from `state_time` to when it was
+    /// received. It includes any difference between the clocks of the Lavalink server and this
+    /// machine, so compare it against previous values rather than reading it as an exact ping.
+    ///
+    /// Contains `Signed 64 bit integer`
+    fn state_ping_ms(&self) -> i64 {
+        self.ping_ms
+    }
 }
 
 #[pyclass]
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct TrackStart {
     pub inner: LavaTrackStart,
+    pub info: Option<LavaInfo>,
 }
 
 #[pymethods]
@@ -680,6 +1379,15 @@ impl TrackStart {
     fn track(&self) -> String {
         self.inner.track.clone()
     }
+
+    #[getter]
+    /// Contains `Option<Info>`
+    ///
+    /// The info of the track that started, taken from the queued track, so there's no need to
+    /// call `Lavalink.decode_track()`. It's `None` if the track was not queued with its info.
+    fn info(&self) -> Option<Info> {
+        self.info.clone().map(|info| Info { inner: info })
+    }
 }
 
 #[pyclass]
@@ -719,6 +1427,57 @@ impl TrackFinish {
     fn reason(&self) -> String {
         self.inner.reason.clone()
     }
+
+    /// If the reason is `FINISHED`, the track played until the end, or until its finish time.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_natural_finish(&self) -> bool {
+        self.inner.reason == "FINISHED"
+    }
+
+    /// If the reason is `LOAD_FAILED`, the track failed to start, and a `track_exception` event
+    /// was sent before this one.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_load_failed(&self) -> bool {
+        self.inner.reason == "LOAD_FAILED"
+    }
+
+    /// If the reason is `STOPPED`, the player was stopped, like with `Lavalink.stop()`.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_stopped(&self) -> bool {
+        self.inner.reason == "STOPPED"
+    }
+
+    /// If the reason is `REPLACED`, another track started playing in its place.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_replaced(&self) -> bool {
+        self.inner.reason == "REPLACED"
+    }
+
+    /// If the reason is `CLEANUP`, the player was cleaned up by the Lavalink server.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_cleanup(&self) -> bool {
+        self.inner.reason == "CLEANUP"
+    }
+
+    /// If the next track of the queue should start, which is only when the track finished by
+    /// itself or failed to load. Stopped, replaced and cleaned up tracks were ended on purpose,
+    /// so starting the next one would skip a track.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn may_start_next(&self) -> bool {
+        self.is_natural_finish() || self.is_load_failed()
+    }
 }
 
 #[pyclass]
@@ -754,9 +1513,12 @@ impl TrackException {
     }
 
     #[getter]
+    /// The error message of the exception, this is the same as `exception_message`, as Lavalink
+    /// doesn't send a separate error field.
+    ///
     /// Contains `String`
     fn error(&self) -> String {
-        self.inner.track.clone()
+        self.inner.exception.message.clone()
     }
 
     #[getter]
@@ -776,6 +1538,32 @@ impl TrackException {
     fn exception_message(&self) -> String {
         self.inner.exception.message.clone()
     }
+
+    /// If the severity is `COMMON`, the cause is known and expected, like a video not being
+    /// available.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_common(&self) -> bool {
+        self.inner.exception.severity == "COMMON"
+    }
+
+    /// If the severity is `SUSPICIOUS`, the cause might not be exactly known, but is possibly
+    /// caused by outside factors, like the source website changing.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_suspicious(&self) -> bool {
+        self.inner.exception.severity == "SUSPICIOUS"
+    }
+
+    /// If the severity is `FAULT`, the probable cause is an issue with Lavalink or a bug in it.
+    ///
+    /// Returns `bool`
+    #[pyo3(text_signature = "($self, /)")]
+    fn is_fault(&self) -> bool {
+        self.inner.exception.severity == "FAULT"
+    }
 }
 
 #[pyclass]
@@ -900,3 +1688,109 @@ impl PlayerDestroyed {
         self.inner.cleanup
     }
 }
+
+/// Sent by lavasnek_rs itself, not by the Lavalink server, when a guild was idle for the time set
+/// with `LavalinkBuilder.set_idle_timeout_secs()`.
+#[pyclass]
+#[derive(Clone)]
+pub struct IdleTimeout {
+    pub guild_id: u64,
+    pub disconnected: bool,
+}
+
+#[pymethods]
+impl IdleTimeout {
+    #[getter]
+    /// Contains `Unsigned 64 bit integer`
+    fn guild_id(&self) -> u64 {
+        self.guild_id
+    }
+
+    #[getter]
+    /// If the session was destroyed and the bot left the voice channel, because
+    /// `LavalinkBuilder.set_idle_disconnect()` is enabled.
+    ///
+    /// Contains `bool`
+    fn disconnected(&self) -> bool {
+        self.disconnected
+    }
+}
+
+/// Sent by lavasnek_rs itself when a related track was queued by autoplay, see
+/// `Lavalink.autoplay()`.
+#[pyclass]
+#[derive(Clone)]
+pub struct AutoplayTrack {
+    pub guild_id: u64,
+    pub track: LavaTrack,
+}
+
+#[pymethods]
+impl AutoplayTrack {
+    #[getter]
+    /// Contains `Unsigned 64 bit integer`
+    fn guild_id(&self) -> u64 {
+        self.guild_id
+    }
+
+    #[getter]
+    /// The track that was queued.
+    ///
+    /// Contains `Track`
+    fn track(&self) -> Track {
+        Track {
+            inner: self.track.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} is not close to {}", a, b);
+    }
+
+    #[test]
+    fn db_to_gain_keeps_0_db_unchanged() {
+        assert_close(db_to_gain(0.0), 0.0);
+    }
+
+    #[test]
+    fn db_to_gain_matches_the_amplitude() {
+        // +6 dB is about twice the amplitude, which is a gain of 0.25.
+        assert_close(db_to_gain(20.0 * 2f64.log10()), 0.25);
+        assert_close(db_to_gain(-20.0 * 2f64.log10()), -0.125);
+    }
+
+    #[test]
+    fn db_to_gain_is_clamped_to_lavalink_bounds() {
+        assert_close(db_to_gain(20.0 * 5f64.log10()), 1.0);
+        assert_close(db_to_gain(100.0), 1.0);
+        assert_close(db_to_gain(f64::INFINITY), 1.0);
+        assert_close(db_to_gain(-1000.0), -0.25);
+        assert_close(db_to_gain(f64::NEG_INFINITY), -0.25);
+    }
+
+    #[test]
+    fn bass_boost_raises_the_lowest_bands() {
+        let bands = Band::bass_boost(6.0);
+
+        let band_numbers = bands.iter().map(|b| b.inner.band).collect::<Vec<_>>();
+        assert_eq!(band_numbers, vec![0, 1, 2, 3]);
+
+        for band in &bands[..3] {
+            assert_close(band.inner.gain, db_to_gain(6.0));
+        }
+
+        assert_close(bands[3].inner.gain, db_to_gain(3.0));
+    }
+
+    #[test]
+    fn bass_boost_is_clamped() {
+        for band in Band::bass_boost(1000.0) {
+            assert_close(band.inner.gain, 1.0);
+        }
+    }
+}
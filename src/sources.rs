@@ -0,0 +1,172 @@
+use crate::error;
+
+use pyo3::prelude::*;
+
+/// A search source, with the prefix Lavalink uses to search on it.
+#[derive(Debug)]
+pub struct Source {
+    /// The name used by `Lavalink.search_tracks_with_source()`.
+    pub name: &'static str,
+    pub prefix: &'static str,
+    /// The name of the source manager on the Lavalink server, for the sources that come from the
+    /// LavaSrc plugin.
+    pub plugin_source_manager: Option<&'static str>,
+    /// The domains of the URLs that are loaded by this source, subdomains included.
+    pub domains: &'static [&'static str],
+}
+
+pub const SOURCES: &[Source] = &[
+    Source {
+        name: "youtube",
+        prefix: "ytsearch",
+        plugin_source_manager: None,
+        domains: &["youtube.com", "youtu.be"],
+    },
+    Source {
+        name: "youtube_music",
+        prefix: "ytmsearch",
+        plugin_source_manager: None,
+        domains: &[],
+    },
+    Source {
+        name: "soundcloud",
+        prefix: "scsearch",
+        plugin_source_manager: None,
+        domains: &["soundcloud.com"],
+    },
+    Source {
+        name: "spotify",
+        prefix: "spsearch",
+        plugin_source_manager: Some("spotify"),
+        domains: &["open.spotify.com", "spotify.link"],
+    },
+    Source {
+        name: "apple_music",
+        prefix: "amsearch",
+        plugin_source_manager: Some("applemusic"),
+        domains: &["music.apple.com"],
+    },
+    Source {
+        name: "deezer",
+        prefix: "dzsearch",
+        plugin_source_manager: Some("deezer"),
+        domains: &["deezer.com", "deezer.page.link"],
+    },
+];
+
+pub fn source_by_name(name: &str) -> PyResult<&'static Source> {
+    SOURCES
+        .iter()
+        .find(|source| source.name == name)
+        .ok_or_else(|| {
+            let names = SOURCES.iter().map(|source| source.name).collect::<Vec<_>>();
+
+            error::ValueError::new_err(format!(
+                "Unknown source {:?}, expected one of {:?}",
+                name, names
+            ))
+        })
+}
+
+/// If the query is loaded as a URL rather than searched.
+pub fn is_url(query: &str) -> bool {
+    query.starts_with("https://") || query.starts_with("http://")
+}
+
+/// Returns the source that loads the URL, if any.
+///
+/// YouTube Music URLs are returned as `youtube`, as they are on a subdomain of YouTube.
+pub fn source_for_url(url: &str) -> Option<&'static Source> {
+    let without_scheme = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let host = without_scheme
+        .split(|c| c == '/' || c == '?' || c == '#')
+        .next()?
+        .to_lowercase();
+
+    SOURCES.iter().find(|source| {
+        source
+            .domains
+            .iter()
+            .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+    })
+}
+
+/// Returns the LavaSrc source that loads the URL, if any.
+pub fn plugin_source_for_url(url: &str) -> Option<&'static Source> {
+    source_for_url(url).filter(|source| source.plugin_source_manager.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_name(url: &str) -> Option<&'static str> {
+        source_for_url(url).map(|source| source.name)
+    }
+
+    #[test]
+    fn source_for_url_matches_domains() {
+        assert_eq!(
+            source_name("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+            Some("youtube")
+        );
+        assert_eq!(source_name("https://youtu.be/dQw4w9WgXcQ"), Some("youtube"));
+        assert_eq!(
+            source_name("https://soundcloud.com/rick-astley-official"),
+            Some("soundcloud")
+        );
+        assert_eq!(
+            source_name("https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT"),
+            Some("spotify")
+        );
+        assert_eq!(
+            source_name("https://music.apple.com/us/album/1558533900"),
+            Some("apple_music")
+        );
+        assert_eq!(
+            source_name("http://www.deezer.com/track/781592622"),
+            Some("deezer")
+        );
+    }
+
+    #[test]
+    fn source_for_url_matches_subdomains_as_the_parent_source() {
+        assert_eq!(
+            source_name("https://music.youtube.com/watch?v=dQw4w9WgXcQ"),
+            Some("youtube")
+        );
+        assert_eq!(
+            source_name("https://m.soundcloud.com/x"),
+            Some("soundcloud")
+        );
+    }
+
+    #[test]
+    fn source_for_url_only_looks_at_the_host() {
+        assert_eq!(source_name("https://YOUTU.BE/x"), Some("youtube"));
+        assert_eq!(source_name("https://youtu.be?t=1"), Some("youtube"));
+        assert_eq!(source_name("https://example.com/youtube.com"), None);
+        assert_eq!(source_name("https://notyoutube.com/watch"), None);
+        assert_eq!(source_name("https://youtube.com.example.com/watch"), None);
+    }
+
+    #[test]
+    fn source_for_url_rejects_non_urls() {
+        assert_eq!(source_name("youtube.com/watch?v=dQw4w9WgXcQ"), None);
+        assert_eq!(source_name("never gonna give you up"), None);
+        assert_eq!(source_name(""), None);
+    }
+
+    #[test]
+    fn plugin_source_for_url_only_returns_lavasrc_sources() {
+        let plugin_source = |url| plugin_source_for_url(url).map(|source| source.name);
+
+        assert_eq!(
+            plugin_source("https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT"),
+            Some("spotify")
+        );
+        assert_eq!(plugin_source("https://youtu.be/dQw4w9WgXcQ"), None);
+    }
+}
@@ -0,0 +1,16 @@
+import lavasnek_rs
+
+
+def test_track_equality():
+    assert lavasnek_rs.Track("x") == lavasnek_rs.Track("x")
+    assert lavasnek_rs.Track("x") != lavasnek_rs.Track("y")
+    assert lavasnek_rs.Track("x") != "x"
+
+
+def test_track_hash():
+    assert len({lavasnek_rs.Track("x"), lavasnek_rs.Track("x")}) == 1
+    assert len({lavasnek_rs.Track("x"), lavasnek_rs.Track("y")}) == 2
+
+
+def test_track_repr():
+    assert repr(lavasnek_rs.Track("x")) == 'Track(track="x", info=None)'